@@ -16,6 +16,7 @@ fn main() {
                 resizable: true,
             },
             vsync: Vsync::Disabled,
+            context: ContextSettings::default(),
         },
         run,
     );
@@ -45,7 +46,10 @@ fn run(engine: &mut Engine) -> impl FnMut(InputMessage, &mut Engine) {
 
     move |event, engine| match event {
         InputMessage::CloseRequested => engine.stop(),
-        InputMessage::KeyPressed(key) => match key {
+        InputMessage::KeyPressed {
+            key,
+            ..
+        } => match key {
             KeyboardButton::Escape => engine.stop(),
             _ => {}
         },
@@ -72,11 +76,11 @@ fn run(engine: &mut Engine) -> impl FnMut(InputMessage, &mut Engine) {
                 screen.set_transform(&screen_transform);
             }
         }
-        InputMessage::CursorScroll(direction) => {
-            match direction {
-                ScrollDirection::Up => screen_transform.scale *= 1.1,
-                ScrollDirection::Down => screen_transform.scale /= 1.1,
-                _ => {}
+        InputMessage::CursorScroll(delta) => {
+            if delta.y > 0.0 {
+                screen_transform.scale *= 1.1;
+            } else if delta.y < 0.0 {
+                screen_transform.scale /= 1.1;
             }
             screen.set_transform(&screen_transform);
         }
@@ -16,6 +16,7 @@ fn main() {
                 resizable: true,
             },
             vsync: Vsync::Disabled,
+            context: ContextSettings::default(),
         },
         run,
     );
@@ -52,7 +53,10 @@ fn run(engine: &mut Engine) -> impl FnMut(InputMessage, &mut Engine) {
 
     move |event, engine| match event {
         InputMessage::CloseRequested => engine.stop(),
-        InputMessage::KeyPressed(key) => match key {
+        InputMessage::KeyPressed {
+            key,
+            ..
+        } => match key {
             KeyboardButton::Escape => engine.stop(),
             _ => {}
         },
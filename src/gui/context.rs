@@ -0,0 +1,316 @@
+use crate::gui::GuiStyle;
+use crate::math::AABB2D;
+use crate::render::Layer;
+use crate::types::*;
+use crate::{CursorButton, Engine, InputMessage};
+use cgmath::*;
+use core::ops::Range;
+
+/// The depth between consecutive widgets.
+const DEPTH_STEP: f32 = 1.0 / 65536.0;
+
+/// An immediate mode GUI. Widgets are declared every frame by calling their functions, which draw
+/// the widget and return how it was interacted with that frame. There's no retained widget tree,
+/// widgets are identified by the order they're declared in.
+///
+/// Widgets declared later are drawn on top of earlier ones, and when widgets overlap the topmost
+/// one under the cursor receives the click. Since a widget can't know what will be declared after
+/// it, this is decided using the positions widgets had in the previous frame.
+///
+/// Positions are in the same units as cursor positions, so the GUI should be drawn to a layer with
+/// the default transform, with cursor positions reported in pixels.
+pub struct Gui {
+    style: GuiStyle,
+    cursor: Vector2<f32>,
+    down: bool,
+    pressed: bool,
+    released: bool,
+    next_id: u32,
+    hot: Option<u32>,
+    resolved: bool,
+    active: Option<u32>,
+    rects: Vec<(u32, AABB2D)>,
+    last_rects: Vec<(u32, AABB2D)>,
+    sprites: Vec<Sprite>,
+    texts: Vec<Text>,
+}
+
+impl Gui {
+    /// Creates a new GUI drawn with the given style.
+    pub fn new(style: GuiStyle) -> Gui {
+        Gui {
+            style,
+            cursor: Vector2::zero(),
+            down: false,
+            pressed: false,
+            released: false,
+            next_id: 0,
+            hot: None,
+            resolved: false,
+            active: None,
+            rects: Vec::new(),
+            last_rects: Vec::new(),
+            sprites: Vec::new(),
+            texts: Vec::new(),
+        }
+    }
+
+    /// Gets the style the GUI is drawn with.
+    pub fn style(&mut self) -> &mut GuiStyle {
+        &mut self.style
+    }
+
+    /// Processes an input message. Every cursor message should be pushed before the widgets for
+    /// the frame are declared.
+    pub fn push(&mut self, message: &InputMessage) {
+        match *message {
+            InputMessage::CursorMoved {
+                pos,
+                ..
+            } => self.cursor = pos,
+            InputMessage::CursorPressed {
+                button: CursorButton::Left,
+                pos,
+            } => {
+                self.cursor = pos;
+                self.down = true;
+                self.pressed = true;
+            }
+            InputMessage::CursorReleased {
+                button: CursorButton::Left,
+                pos,
+            } => {
+                self.cursor = pos;
+                self.down = false;
+                self.released = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// If the cursor is over a widget. This is useful for deciding if a click should be handled by
+    /// the game instead.
+    pub fn is_hovered(&mut self) -> bool {
+        self.resolve();
+        self.hot.is_some()
+    }
+
+    /// Draws text with its top left corner at the position.
+    pub fn label(&mut self, pos: Vector2<f32>, text: &str) {
+        let depth = self.next_depth();
+        self.text(pos, text, depth, HorizontalAlign::Left, VerticalAlign::Top);
+    }
+
+    /// Draws a button with a centered label. Returns true if the button was clicked this frame,
+    /// which is when the cursor is pressed and released over it.
+    pub fn button(&mut self, rect: AABB2D, label: &str) -> bool {
+        let (id, hovered, depth) = self.widget(rect);
+        let active = self.active == Some(id);
+        let color = if active && self.down {
+            self.style.button_pressed
+        } else if hovered {
+            self.style.button_hovered
+        } else {
+            self.style.button
+        };
+        self.rect(rect, color, depth);
+        let center = (rect.min + rect.max) / 2.0;
+        self.text(center, label, depth + DEPTH_STEP / 2.0, HorizontalAlign::Center, VerticalAlign::Middle);
+        active && hovered && self.released
+    }
+
+    /// Draws a horizontal slider for the value within the range. Pressing on the slider and
+    /// dragging moves its handle. Returns true if the value changed this frame.
+    pub fn slider(&mut self, rect: AABB2D, value: &mut f32, range: Range<f32>) -> bool {
+        let (id, _, depth) = self.widget(rect);
+        let width = rect.max.x - rect.min.x;
+        let mut changed = false;
+        if self.active == Some(id) && (self.down || self.released) && width > 0.0 {
+            let t = ((self.cursor.x - rect.min.x) / width).clamp(0.0, 1.0);
+            let next = range.start + t * (range.end - range.start);
+            if next != *value {
+                *value = next;
+                changed = true;
+            }
+        }
+
+        self.rect(rect, self.style.slider_track, depth);
+        let span = range.end - range.start;
+        let t = if span != 0.0 {
+            ((*value - range.start) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let handle_width = self.style.slider_handle_width.min(width);
+        let x = rect.min.x + t * (width - handle_width);
+        let handle = AABB2D::new(x, rect.min.y, x + handle_width, rect.max.y);
+        self.rect(handle, self.style.slider_handle, depth + DEPTH_STEP / 2.0);
+        changed
+    }
+
+    /// Draws the widgets declared this frame to the layer, then starts the next frame.
+    pub fn draw(&mut self, engine: &mut Engine, layer: &mut Layer) {
+        engine.text_append(&self.texts, &mut self.sprites);
+        layer.set_sprites(&self.sprites);
+        self.finish();
+    }
+
+    /// Starts the next frame without drawing.
+    fn finish(&mut self) {
+        if self.released {
+            self.active = None;
+        }
+        self.pressed = false;
+        self.released = false;
+        self.next_id = 0;
+        self.resolved = false;
+        core::mem::swap(&mut self.rects, &mut self.last_rects);
+        self.rects.clear();
+        self.sprites.clear();
+        self.texts.clear();
+    }
+
+    /// Finds the topmost widget under the cursor using last frame's positions.
+    fn resolve(&mut self) {
+        if !self.resolved {
+            self.resolved = true;
+            let cursor = self.cursor;
+            self.hot = self
+                .last_rects
+                .iter()
+                .rev()
+                .find(|(_, rect)| rect.contains_point(&cursor))
+                .map(|(id, _)| *id);
+        }
+    }
+
+    /// Declares an interactive widget, returning its id, if it's the topmost widget under the
+    /// cursor, and its depth.
+    fn widget(&mut self, rect: AABB2D) -> (u32, bool, f32) {
+        self.resolve();
+        let id = self.next_id;
+        let depth = self.next_depth();
+        self.rects.push((id, rect));
+        let hovered = self.hot == Some(id);
+        if hovered && self.pressed {
+            self.active = Some(id);
+        }
+        (id, hovered, depth)
+    }
+
+    fn next_depth(&mut self) -> f32 {
+        let depth = self.style.depth + self.next_id as f32 * DEPTH_STEP;
+        self.next_id += 1;
+        depth
+    }
+
+    fn rect(&mut self, rect: AABB2D, color: RGBA8, depth: f32) {
+        let size = rect.max - rect.min;
+        self.sprites.push(Sprite::new(rect.min.extend(depth), size, Texture::default(), color, 0.0));
+    }
+
+    fn text(
+        &mut self,
+        pos: Vector2<f32>,
+        text: &str,
+        depth: f32,
+        horizontal: HorizontalAlign,
+        vertical: VerticalAlign,
+    ) {
+        self.texts.push(Text {
+            string: String::from(text),
+            pos: pos.extend(depth),
+            scale: self.style.text_scale,
+            color: self.style.text,
+            horizontal_align: horizontal,
+            vertical_align: vertical,
+            ..Text::default()
+        });
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(gui: &mut Gui, pos: Vector2<f32>) {
+        gui.push(&InputMessage::CursorPressed {
+            button: CursorButton::Left,
+            pos,
+        });
+        gui.push(&InputMessage::CursorReleased {
+            button: CursorButton::Left,
+            pos,
+        });
+    }
+
+    #[test]
+    fn topmost_wins() {
+        let mut gui = Gui::new(GuiStyle::default());
+        let bottom = AABB2D::new(0.0, 0.0, 100.0, 100.0);
+        let top = AABB2D::new(50.0, 50.0, 150.0, 150.0);
+
+        // The first frame records where the widgets are.
+        assert!(!gui.button(bottom, "Bottom"));
+        assert!(!gui.button(top, "Top"));
+        gui.finish();
+
+        // A click where they overlap only goes to the top button.
+        click(&mut gui, Vector2::new(75.0, 75.0));
+        assert!(!gui.button(bottom, "Bottom"));
+        assert!(gui.button(top, "Top"));
+        gui.finish();
+
+        // A click only on the bottom button goes to it.
+        click(&mut gui, Vector2::new(25.0, 25.0));
+        assert!(gui.button(bottom, "Bottom"));
+        assert!(!gui.button(top, "Top"));
+        gui.finish();
+
+        // Pressing on one button and releasing on another clicks neither.
+        gui.push(&InputMessage::CursorPressed {
+            button: CursorButton::Left,
+            pos: Vector2::new(25.0, 25.0),
+        });
+        assert!(!gui.button(bottom, "Bottom"));
+        assert!(!gui.button(top, "Top"));
+        gui.finish();
+        gui.push(&InputMessage::CursorReleased {
+            button: CursorButton::Left,
+            pos: Vector2::new(125.0, 125.0),
+        });
+        assert!(!gui.button(bottom, "Bottom"));
+        assert!(!gui.button(top, "Top"));
+        gui.finish();
+    }
+
+    #[test]
+    fn slider() {
+        let mut gui = Gui::new(GuiStyle::default());
+        let rect = AABB2D::new(0.0, 0.0, 100.0, 20.0);
+        let mut value = 0.0;
+        assert!(!gui.slider(rect, &mut value, 0.0..10.0));
+        gui.finish();
+
+        gui.push(&InputMessage::CursorPressed {
+            button: CursorButton::Left,
+            pos: Vector2::new(50.0, 10.0),
+        });
+        assert!(gui.slider(rect, &mut value, 0.0..10.0));
+        assert_eq!(value, 5.0);
+        gui.finish();
+
+        // Dragging outside of the slider keeps moving it, clamped to the range.
+        gui.push(&InputMessage::CursorMoved {
+            pos: Vector2::new(200.0, 10.0),
+            delta: Vector2::new(150.0, 0.0),
+        });
+        assert!(gui.slider(rect, &mut value, 0.0..10.0));
+        assert_eq!(value, 10.0);
+        gui.finish();
+    }
+}
@@ -0,0 +1,5 @@
+mod context;
+mod style;
+
+pub use self::context::*;
+pub use self::style::*;
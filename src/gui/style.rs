@@ -0,0 +1,41 @@
+use crate::colors;
+use crate::types::RGBA8;
+
+/// Colors and sizes used to draw the GUI.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GuiStyle {
+    /// Color of a button that isn't being interacted with.
+    pub button: RGBA8,
+    /// Color of a button under the cursor.
+    pub button_hovered: RGBA8,
+    /// Color of a button being pressed.
+    pub button_pressed: RGBA8,
+    /// Color of a slider's track.
+    pub slider_track: RGBA8,
+    /// Color of a slider's handle.
+    pub slider_handle: RGBA8,
+    /// Width of a slider's handle. Units are measured in pixels.
+    pub slider_handle_width: f32,
+    /// Color of text.
+    pub text: RGBA8,
+    /// Scale of text as defined by the font.
+    pub text_scale: u32,
+    /// Depth of the first widget. Each widget is drawn slightly above the widgets before it.
+    pub depth: f32,
+}
+
+impl Default for GuiStyle {
+    fn default() -> GuiStyle {
+        GuiStyle {
+            button: RGBA8::new_raw(70, 70, 80, 255),
+            button_hovered: RGBA8::new_raw(95, 95, 110, 255),
+            button_pressed: RGBA8::new_raw(50, 50, 60, 255),
+            slider_track: RGBA8::new_raw(50, 50, 60, 255),
+            slider_handle: RGBA8::new_raw(140, 140, 160, 255),
+            slider_handle_width: 10.0,
+            text: colors::WHITE,
+            text_scale: 16,
+            depth: 0.0,
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
+
+/// Declares the engine's keyboard enum with conversions from and to the windowing library's key
+/// codes. Every variant must exist on both sides, so the conversions are exhaustive and a missing
+/// key is a compile error.
+macro_rules! keyboard_buttons {
+    ($($(#[$meta:meta])* $name:ident,)*) => {
+        /// Symbolic name for a keyboard key.
+        #[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, PartialEq, Eq)]
+        pub enum KeyboardButton {
+            $($(#[$meta])* $name,)*
+        }
+
+        impl KeyboardButton {
+            /// Every keyboard button.
+            pub(crate) const ALL: &'static [KeyboardButton] = &[$(KeyboardButton::$name,)*];
+        }
+
+        impl From<VirtualKeyCode> for KeyboardButton {
+            fn from(key: VirtualKeyCode) -> KeyboardButton {
+                match key {
+                    $(VirtualKeyCode::$name => KeyboardButton::$name,)*
+                }
+            }
+        }
+
+        impl From<KeyboardButton> for VirtualKeyCode {
+            fn from(key: KeyboardButton) -> VirtualKeyCode {
+                match key {
+                    $(KeyboardButton::$name => VirtualKeyCode::$name,)*
+                }
+            }
+        }
+    };
+}
+
+keyboard_buttons! {
+    /// The '1' key over the letters.
+    Key1,
+    /// The '2' key over the letters.
+    Key2,
+    /// The '3' key over the letters.
+    Key3,
+    /// The '4' key over the letters.
+    Key4,
+    /// The '5' key over the letters.
+    Key5,
+    /// The '6' key over the letters.
+    Key6,
+    /// The '7' key over the letters.
+    Key7,
+    /// The '8' key over the letters.
+    Key8,
+    /// The '9' key over the letters.
+    Key9,
+    /// The '0' key over the 'O' and 'P' keys.
+    Key0,
+
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+
+    /// The Escape key, next to F1.
+    Escape,
+
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+
+    /// Print Screen/SysRq.
+    Snapshot,
+    /// Scroll Lock.
+    Scroll,
+    /// Pause/Break key, next to Scroll lock.
+    Pause,
+
+    /// `Insert`, next to Backspace.
+    Insert,
+    Home,
+    Delete,
+    End,
+    PageDown,
+    PageUp,
+
+    Left,
+    Up,
+    Right,
+    Down,
+
+    /// The Backspace key, right over Enter.
+    Back,
+    /// The Enter key.
+    Return,
+    /// The space bar.
+    Space,
+
+    /// The "Compose" key on Linux.
+    Compose,
+
+    Caret,
+
+    Numlock,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadComma,
+    NumpadEnter,
+    NumpadEquals,
+    NumpadMultiply,
+    NumpadSubtract,
+
+    AbntC1,
+    AbntC2,
+    Apostrophe,
+    Apps,
+    Asterisk,
+    At,
+    Ax,
+    Backslash,
+    Calculator,
+    Capital,
+    Colon,
+    Comma,
+    Convert,
+    Equals,
+    Grave,
+    Kana,
+    Kanji,
+    LAlt,
+    LBracket,
+    LControl,
+    LShift,
+    LWin,
+    Mail,
+    MediaSelect,
+    MediaStop,
+    Minus,
+    Mute,
+    MyComputer,
+    /// Also called "Next".
+    NavigateForward,
+    /// Also called "Prior".
+    NavigateBackward,
+    NextTrack,
+    NoConvert,
+    OEM102,
+    Period,
+    PlayPause,
+    Plus,
+    Power,
+    PrevTrack,
+    RAlt,
+    RBracket,
+    RControl,
+    RShift,
+    RWin,
+    Semicolon,
+    Slash,
+    Sleep,
+    Stop,
+    Sysrq,
+    Tab,
+    Underline,
+    Unlabeled,
+    VolumeDown,
+    VolumeUp,
+    Wake,
+    WebBack,
+    WebFavorites,
+    WebForward,
+    WebHome,
+    WebRefresh,
+    WebSearch,
+    WebStop,
+    Yen,
+    Copy,
+    Paste,
+    Cut,
+}
+
+/// Describes a button of a mouse controller.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum CursorButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+impl From<MouseButton> for CursorButton {
+    fn from(button: MouseButton) -> CursorButton {
+        match button {
+            MouseButton::Left => CursorButton::Left,
+            MouseButton::Right => CursorButton::Right,
+            MouseButton::Middle => CursorButton::Middle,
+            MouseButton::Other(id) => CursorButton::Other(id),
+        }
+    }
+}
+
+impl From<CursorButton> for MouseButton {
+    fn from(button: CursorButton) -> MouseButton {
+        match button {
+            CursorButton::Left => MouseButton::Left,
+            CursorButton::Right => MouseButton::Right,
+            CursorButton::Middle => MouseButton::Middle,
+            CursorButton::Other(id) => MouseButton::Other(id),
+        }
+    }
+}
+
+/// The modifier keys held at the time of a keyboard event. Either the left or right version of a
+/// key counts as held.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Modifiers {
+    /// Either shift key is held.
+    pub shift: bool,
+    /// Either control key is held.
+    pub ctrl: bool,
+    /// Either alt key is held.
+    pub alt: bool,
+    /// Either logo key is held. This is the Windows key on PC and the command key on Mac.
+    pub logo: bool,
+}
+
+impl From<ModifiersState> for Modifiers {
+    fn from(state: ModifiersState) -> Modifiers {
+        Modifiers {
+            shift: state.shift(),
+            ctrl: state.ctrl(),
+            alt: state.alt(),
+            logo: state.logo(),
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyboard_round_trip() {
+        for &key in KeyboardButton::ALL {
+            assert_eq!(KeyboardButton::from(VirtualKeyCode::from(key)), key);
+        }
+        assert_eq!(KeyboardButton::from(VirtualKeyCode::Key1), KeyboardButton::Key1);
+        assert_eq!(KeyboardButton::from(VirtualKeyCode::Cut), KeyboardButton::Cut);
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        for &button in
+            &[CursorButton::Left, CursorButton::Right, CursorButton::Middle, CursorButton::Other(7)]
+        {
+            assert_eq!(CursorButton::from(MouseButton::from(button)), button);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::input::{InputMessage, KeyboardButton};
+
+/// Recognizes a combination of keys being held at the same time, such as a shortcut. The chord
+/// fires once when the last of its keys is pressed, regardless of the order the keys were pressed
+/// in, and can't fire again until one of its keys is released.
+pub struct KeyChord {
+    keys: Vec<KeyboardButton>,
+    held: Vec<bool>,
+    active: bool,
+}
+
+impl KeyChord {
+    /// Creates a new chord from the given keys. Duplicate keys are ignored.
+    pub fn new(keys: &[KeyboardButton]) -> KeyChord {
+        let mut unique = Vec::with_capacity(keys.len());
+        for &key in keys {
+            if !unique.contains(&key) {
+                unique.push(key);
+            }
+        }
+        let held = vec![false; unique.len()];
+        KeyChord {
+            keys: unique,
+            held,
+            active: false,
+        }
+    }
+
+    /// If every key in the chord is currently held.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Processes an input message, returning true if it completed the chord.
+    pub fn push(&mut self, message: &InputMessage) -> bool {
+        match *message {
+            InputMessage::KeyPressed {
+                key,
+                ..
+            } => {
+                if let Some(index) = self.keys.iter().position(|&chord_key| chord_key == key) {
+                    self.held[index] = true;
+                    if !self.active && self.held.iter().all(|&held| held) {
+                        self.active = true;
+                        return true;
+                    }
+                }
+                false
+            }
+            InputMessage::KeyReleased {
+                key,
+                ..
+            } => {
+                if let Some(index) = self.keys.iter().position(|&chord_key| chord_key == key) {
+                    self.held[index] = false;
+                    self.active = false;
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use KeyboardButton::*;
+
+    fn pressed(key: KeyboardButton) -> InputMessage {
+        InputMessage::KeyPressed {
+            key,
+            modifiers: Default::default(),
+        }
+    }
+
+    fn released(key: KeyboardButton) -> InputMessage {
+        InputMessage::KeyReleased {
+            key,
+            modifiers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn chord() {
+        let mut chord = KeyChord::new(&[LControl, LShift, S]);
+        assert!(!chord.push(&pressed(S)));
+        assert!(!chord.push(&pressed(LShift)));
+        assert!(!chord.push(&pressed(A)));
+        assert!(chord.push(&pressed(LControl)));
+        assert!(chord.is_active());
+
+        // Key repeat doesn't fire again.
+        assert!(!chord.push(&pressed(LControl)));
+
+        // Releasing and pressing a key fires again.
+        assert!(!chord.push(&released(S)));
+        assert!(!chord.is_active());
+        assert!(chord.push(&pressed(S)));
+    }
+}
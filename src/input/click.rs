@@ -0,0 +1,138 @@
+use crate::input::{CursorButton, InputMessage};
+use crate::time::Instant;
+use cgmath::prelude::*;
+use cgmath::*;
+use core::time::Duration;
+
+/// A click event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ClickEvent {
+    /// A button was pressed twice within the interval and radius. Contains the button and the
+    /// position of the second press.
+    DoubleClick(CursorButton, Vector2<f32>),
+    /// A button was released.
+    Released {
+        /// Button released.
+        button: CursorButton,
+        /// Cursor position at time of release.
+        pos: Vector2<f32>,
+        /// How long the button was held.
+        held: Duration,
+    },
+}
+
+/// Recognizes double clicks and measures how long buttons are held from input messages. A press
+/// counts as a double click when it's the same button as the previous press, within the interval
+/// after it, and within the radius of it. A third press starts a new double click rather than
+/// completing another.
+pub struct ClickRecognizer {
+    interval: Duration,
+    radius: f32,
+    last_press: Option<(CursorButton, Instant, Vector2<f32>)>,
+    held: Vec<(CursorButton, Instant)>,
+}
+
+impl ClickRecognizer {
+    /// Creates a new recognizer. The radius is in the same units as the cursor positions being
+    /// pushed.
+    pub fn new(interval: Duration, radius: f32) -> ClickRecognizer {
+        ClickRecognizer {
+            interval,
+            radius,
+            last_press: None,
+            held: Vec::new(),
+        }
+    }
+
+    /// Processes an input message received at the given time, returning a click event if one was
+    /// recognized.
+    pub fn push(&mut self, message: &InputMessage, now: Instant) -> Option<ClickEvent> {
+        match *message {
+            InputMessage::CursorPressed {
+                button,
+                pos,
+            } => {
+                self.held.retain(|&(held, _)| held != button);
+                self.held.push((button, now));
+                match self.last_press.take() {
+                    Some((last_button, last_time, last_pos))
+                        if last_button == button
+                            && now - last_time <= self.interval
+                            && (pos - last_pos).magnitude() <= self.radius =>
+                    {
+                        Some(ClickEvent::DoubleClick(button, pos))
+                    }
+                    _ => {
+                        self.last_press = Some((button, now, pos));
+                        None
+                    }
+                }
+            }
+            InputMessage::CursorReleased {
+                button,
+                pos,
+            } => {
+                let index = self.held.iter().position(|&(held, _)| held == button)?;
+                let (_, pressed) = self.held.swap_remove(index);
+                Some(ClickEvent::Released {
+                    button,
+                    pos,
+                    held: now - pressed,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pressed(button: CursorButton, x: f32) -> InputMessage {
+        InputMessage::CursorPressed {
+            button,
+            pos: Vector2::new(x, 0.0),
+        }
+    }
+
+    fn released(button: CursorButton, x: f32) -> InputMessage {
+        InputMessage::CursorReleased {
+            button,
+            pos: Vector2::new(x, 0.0),
+        }
+    }
+
+    #[test]
+    fn clicks() {
+        let mut clicks = ClickRecognizer::new(Duration::from_millis(300), 4.0);
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        assert_eq!(clicks.push(&pressed(CursorButton::Left, 0.0), at(0)), None);
+        assert_eq!(
+            clicks.push(&released(CursorButton::Left, 0.0), at(80)),
+            Some(ClickEvent::Released {
+                button: CursorButton::Left,
+                pos: Vector2::new(0.0, 0.0),
+                held: Duration::from_millis(80),
+            })
+        );
+        assert_eq!(
+            clicks.push(&pressed(CursorButton::Left, 3.0), at(200)),
+            Some(ClickEvent::DoubleClick(CursorButton::Left, Vector2::new(3.0, 0.0)))
+        );
+
+        // A third press doesn't complete another double click.
+        assert_eq!(clicks.push(&pressed(CursorButton::Left, 3.0), at(250)), None);
+
+        // Presses too far apart in time or space aren't double clicks.
+        assert_eq!(clicks.push(&pressed(CursorButton::Left, 3.0), at(600)), None);
+        assert_eq!(clicks.push(&pressed(CursorButton::Left, 10.0), at(650)), None);
+        assert_eq!(clicks.push(&pressed(CursorButton::Right, 10.0), at(700)), None);
+    }
+}
@@ -1,57 +1,110 @@
-use crate::input::{InputMessage, ScrollDirection};
+use crate::input::{CursorSpace, InputMessage, Modifiers};
+use crate::math::to_ndc;
 use crate::Engine;
 use cgmath::prelude::*;
 use cgmath::*;
 use winit::event::WindowEvent;
 
+/// Converts window events into input messages, tracking the window size and cursor position
+/// between events.
 pub struct InputConverter {
     window_size: Vector2<f32>,
     cursor_pos: Vector2<f32>,
+    /// If the cursor position is known, cleared when the cursor leaves the window.
+    cursor_tracked: bool,
+    scroll_line_size: f32,
+    modifiers: Modifiers,
 }
 
 impl InputConverter {
+    /// Creates a new converter for a window of the given physical size.
     pub fn new(window_size: Vector2<f32>) -> InputConverter {
         InputConverter {
             window_size,
             cursor_pos: Vector2::zero(),
+            cursor_tracked: true,
+            scroll_line_size: 20.0,
+            modifiers: Modifiers::default(),
         }
     }
 
-    pub fn push<T: 'static + FnMut(InputMessage, &mut Engine)>(
+    /// Sets the number of pixels a scroll of one line is converted into. The default is 20.
+    pub fn set_scroll_line_size(&mut self, pixels: f32) {
+        self.scroll_line_size = pixels;
+    }
+
+    /// Converts the window event, adding its messages to the end of the queue.
+    pub(crate) fn push(&mut self, event: WindowEvent, engine: &mut Engine, queue: &mut Vec<InputMessage>) {
+        match event {
+            WindowEvent::Resized(_)
+            | WindowEvent::ScaleFactorChanged {
+                ..
+            } => engine.window_check_resize(),
+            WindowEvent::Focused(focused) => engine.window_focused(focused),
+            _ => {}
+        }
+        let cursor_space = engine.cursor_space;
+        self.scroll_line_size = engine.scroll_line_size;
+        self.translate(&event, cursor_space, |message| queue.push(message));
+    }
+
+    /// Converts a window event into input messages, passing each to the output in order. This is
+    /// what the engine's event loop uses, and is exposed for driving an event loop manually. The
+    /// event comes from that event loop, so the caller depends on the same winit version as the
+    /// engine. Some events produce no messages, and some produce several. Cursor positions and
+    /// deltas are reported in the given cursor space.
+    pub fn translate<F: FnMut(InputMessage)>(
         &mut self,
-        event: WindowEvent,
-        event_handler: &mut T,
-        engine: &mut Engine,
+        event: &WindowEvent,
+        cursor_space: CursorSpace,
+        mut output: F,
     ) {
-        match event {
+        match *event {
             // Window
-            WindowEvent::CloseRequested => event_handler(InputMessage::CloseRequested, engine),
+            WindowEvent::CloseRequested => output(InputMessage::CloseRequested),
             WindowEvent::Resized(size) => {
-                engine.window_check_resize();
                 self.window_size = Vector2::new(size.width as f32, size.height as f32);
-                event_handler(InputMessage::WindowResized(self.window_size), engine);
+                output(InputMessage::WindowResized {
+                    width: size.width,
+                    height: size.height,
+                });
             }
             WindowEvent::ScaleFactorChanged {
-                ..
+                scale_factor,
+                ref new_inner_size,
             } => {
-                engine.window_check_resize();
+                // The window is resized to the new size, which may not be followed by a resize event.
+                self.window_size = Vector2::new(new_inner_size.width as f32, new_inner_size.height as f32);
+                output(InputMessage::ScaleFactorChanged(scale_factor as f32));
             }
+            WindowEvent::Focused(focused) => output(InputMessage::WindowFocused(focused)),
 
             // Keyboard
             WindowEvent::ReceivedCharacter(char) => {
-                event_handler(InputMessage::ReceivedCharacter(char), engine);
+                output(InputMessage::ReceivedCharacter(char));
+            }
+            WindowEvent::ModifiersChanged(state) => {
+                self.modifiers = state.into();
             }
             WindowEvent::KeyboardInput {
                 input,
                 ..
             } => {
                 if let Some(keycode) = input.virtual_keycode {
+                    let key = keycode.into();
+                    let modifiers = self.modifiers;
                     match input.state {
                         winit::event::ElementState::Pressed => {
-                            event_handler(InputMessage::KeyPressed(keycode), engine);
+                            output(InputMessage::KeyPressed {
+                                key,
+                                modifiers,
+                            });
                         }
                         winit::event::ElementState::Released => {
-                            event_handler(InputMessage::KeyReleased(keycode), engine);
+                            output(InputMessage::KeyReleased {
+                                key,
+                                modifiers,
+                            });
                         }
                     }
                 }
@@ -66,34 +119,33 @@ impl InputConverter {
                     position.x as f32 - (self.window_size.x / 2.0),
                     -position.y as f32 + (self.window_size.y / 2.0),
                 );
-                let delta = cursor_pos - self.cursor_pos;
+                // The first move after the cursor re-enters the window has no previous position to
+                // compare against, since the cursor may have moved anywhere while it was outside.
+                let delta = if self.cursor_tracked {
+                    cursor_pos - self.cursor_pos
+                } else {
+                    Vector2::zero()
+                };
                 self.cursor_pos = cursor_pos;
-                event_handler(
-                    InputMessage::CursorMoved {
-                        pos: self.cursor_pos,
-                        delta,
-                    },
-                    engine,
-                );
+                self.cursor_tracked = true;
+                output(InputMessage::CursorMoved {
+                    pos: self.to_space(cursor_space, self.cursor_pos),
+                    delta: self.to_space(cursor_space, delta),
+                });
             }
             WindowEvent::MouseWheel {
                 delta,
                 ..
             } => {
-                let (x, y) = match delta {
-                    winit::event::MouseScrollDelta::LineDelta(x, y) => (x, y),
-                    winit::event::MouseScrollDelta::PixelDelta(pos) => (pos.x as f32, pos.y as f32),
+                let delta = match delta {
+                    winit::event::MouseScrollDelta::LineDelta(x, y) => {
+                        Vector2::new(x, y) * self.scroll_line_size
+                    }
+                    winit::event::MouseScrollDelta::PixelDelta(pos) => {
+                        Vector2::new(pos.x as f32, pos.y as f32)
+                    }
                 };
-                if x < 0.0 {
-                    event_handler(InputMessage::CursorScroll(ScrollDirection::Left), engine);
-                } else if x > 0.0 {
-                    event_handler(InputMessage::CursorScroll(ScrollDirection::Right), engine);
-                }
-                if y < 0.0 {
-                    event_handler(InputMessage::CursorScroll(ScrollDirection::Down), engine);
-                } else if y > 0.0 {
-                    event_handler(InputMessage::CursorScroll(ScrollDirection::Up), engine);
-                }
+                output(InputMessage::CursorScroll(delta));
             }
             WindowEvent::MouseInput {
                 state,
@@ -101,35 +153,261 @@ impl InputConverter {
                 ..
             } => match state {
                 winit::event::ElementState::Pressed => {
-                    event_handler(
-                        InputMessage::CursorPressed {
-                            button,
-                            pos: self.cursor_pos,
-                        },
-                        engine,
-                    );
+                    output(InputMessage::CursorPressed {
+                        button: button.into(),
+                        pos: self.to_space(cursor_space, self.cursor_pos),
+                    });
                 }
                 winit::event::ElementState::Released => {
-                    event_handler(
-                        InputMessage::CursorReleased {
-                            button,
-                            pos: self.cursor_pos,
-                        },
-                        engine,
-                    );
+                    output(InputMessage::CursorReleased {
+                        button: button.into(),
+                        pos: self.to_space(cursor_space, self.cursor_pos),
+                    });
                 }
             },
             WindowEvent::CursorEntered {
                 ..
             } => {
-                event_handler(InputMessage::CursorEntered, engine);
+                output(InputMessage::CursorEntered);
             }
             WindowEvent::CursorLeft {
                 ..
             } => {
-                event_handler(InputMessage::CursorLeft, engine);
+                self.cursor_tracked = false;
+                output(InputMessage::CursorLeft);
             }
             _ => {}
         }
     }
+
+    /// Converts a position or delta in pixels into the cursor space configured on the engine.
+    fn to_space(&self, cursor_space: CursorSpace, pixels: Vector2<f32>) -> Vector2<f32> {
+        match cursor_space {
+            CursorSpace::Pixels => pixels,
+            CursorSpace::Normalized => to_ndc(pixels, self.window_size),
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::KeyboardButton;
+    use winit::dpi::{PhysicalPosition, PhysicalSize};
+    use winit::event::{
+        DeviceId, ElementState, KeyboardInput, ModifiersState, MouseScrollDelta, TouchPhase, VirtualKeyCode,
+    };
+
+    fn translate(
+        converter: &mut InputConverter,
+        event: WindowEvent,
+        space: CursorSpace,
+    ) -> Vec<InputMessage> {
+        let mut messages = Vec::new();
+        converter.translate(&event, space, |message| messages.push(message));
+        messages
+    }
+
+    #[test]
+    fn cursor() {
+        let mut converter = InputConverter::new(Vector2::new(100.0, 100.0));
+        let device_id = unsafe { DeviceId::dummy() };
+        #[allow(deprecated)]
+        let moved = |x, y| WindowEvent::CursorMoved {
+            device_id,
+            position: PhysicalPosition::new(x, y),
+            modifiers: Default::default(),
+        };
+
+        assert_eq!(
+            translate(&mut converter, moved(75.0, 25.0), CursorSpace::Pixels),
+            vec![InputMessage::CursorMoved {
+                pos: Vector2::new(25.0, 25.0),
+                delta: Vector2::new(25.0, 25.0),
+            }]
+        );
+        assert_eq!(
+            translate(&mut converter, moved(100.0, 100.0), CursorSpace::Normalized),
+            vec![InputMessage::CursorMoved {
+                pos: Vector2::new(1.0, -1.0),
+                delta: Vector2::new(0.5, -1.5),
+            }]
+        );
+    }
+
+    #[test]
+    fn cursor_reentry() {
+        let mut converter = InputConverter::new(Vector2::new(100.0, 100.0));
+        let device_id = unsafe { DeviceId::dummy() };
+        #[allow(deprecated)]
+        let moved = |x, y| WindowEvent::CursorMoved {
+            device_id,
+            position: PhysicalPosition::new(x, y),
+            modifiers: Default::default(),
+        };
+
+        translate(&mut converter, moved(50.0, 50.0), CursorSpace::Pixels);
+        assert_eq!(
+            translate(
+                &mut converter,
+                WindowEvent::CursorLeft {
+                    device_id
+                },
+                CursorSpace::Pixels
+            ),
+            vec![InputMessage::CursorLeft]
+        );
+        translate(
+            &mut converter,
+            WindowEvent::CursorEntered {
+                device_id,
+            },
+            CursorSpace::Pixels,
+        );
+        assert_eq!(
+            translate(&mut converter, moved(0.0, 0.0), CursorSpace::Pixels),
+            vec![InputMessage::CursorMoved {
+                pos: Vector2::new(-50.0, 50.0),
+                delta: Vector2::zero(),
+            }]
+        );
+        assert_eq!(
+            translate(&mut converter, moved(10.0, 0.0), CursorSpace::Pixels),
+            vec![InputMessage::CursorMoved {
+                pos: Vector2::new(-40.0, 50.0),
+                delta: Vector2::new(10.0, 0.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn window() {
+        let mut converter = InputConverter::new(Vector2::new(100.0, 100.0));
+        assert_eq!(
+            translate(&mut converter, WindowEvent::Resized(PhysicalSize::new(200, 50)), CursorSpace::Pixels),
+            vec![InputMessage::WindowResized {
+                width: 200,
+                height: 50,
+            }]
+        );
+        let mut size = PhysicalSize::new(400, 100);
+        let scale_factor_changed = WindowEvent::ScaleFactorChanged {
+            scale_factor: 2.0,
+            new_inner_size: &mut size,
+        };
+        assert_eq!(
+            translate(&mut converter, scale_factor_changed, CursorSpace::Pixels),
+            vec![InputMessage::ScaleFactorChanged(2.0)]
+        );
+
+        // Cursor positions use the new size right away.
+        #[allow(deprecated)]
+        let moved = WindowEvent::CursorMoved {
+            device_id: unsafe { DeviceId::dummy() },
+            position: PhysicalPosition::new(200.0, 50.0),
+            modifiers: Default::default(),
+        };
+        match translate(&mut converter, moved, CursorSpace::Pixels)[..] {
+            [InputMessage::CursorMoved {
+                pos,
+                ..
+            }] => assert_eq!(pos, Vector2::new(0.0, 0.0)),
+            ref messages => panic!("Unexpected messages: {:?}", messages),
+        }
+        assert_eq!(
+            translate(&mut converter, WindowEvent::Focused(false), CursorSpace::Pixels),
+            vec![InputMessage::WindowFocused(false)]
+        );
+
+        // A burst of resizes delivers each size in order, ending with the final size.
+        let mut messages = Vec::new();
+        for width in 1..=64 {
+            let event = WindowEvent::Resized(PhysicalSize::new(width, 32));
+            converter.translate(&event, CursorSpace::Pixels, |message| messages.push(message));
+        }
+        assert_eq!(messages.len(), 64);
+        assert_eq!(
+            messages.last(),
+            Some(&InputMessage::WindowResized {
+                width: 64,
+                height: 32,
+            })
+        );
+    }
+
+    #[test]
+    fn modifiers() {
+        let mut converter = InputConverter::new(Vector2::new(100.0, 100.0));
+        let device_id = unsafe { DeviceId::dummy() };
+        #[allow(deprecated)]
+        let key = |state| WindowEvent::KeyboardInput {
+            device_id,
+            input: KeyboardInput {
+                scancode: 0,
+                state,
+                virtual_keycode: Some(VirtualKeyCode::S),
+                modifiers: Default::default(),
+            },
+            is_synthetic: false,
+        };
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Default::default()
+        };
+
+        translate(&mut converter, WindowEvent::ModifiersChanged(ModifiersState::CTRL), CursorSpace::Pixels);
+        assert_eq!(
+            translate(&mut converter, key(ElementState::Pressed), CursorSpace::Pixels),
+            vec![InputMessage::KeyPressed {
+                key: KeyboardButton::S,
+                modifiers: ctrl,
+            }]
+        );
+        translate(
+            &mut converter,
+            WindowEvent::ModifiersChanged(ModifiersState::empty()),
+            CursorSpace::Pixels,
+        );
+        assert_eq!(
+            translate(&mut converter, key(ElementState::Released), CursorSpace::Pixels),
+            vec![InputMessage::KeyReleased {
+                key: KeyboardButton::S,
+                modifiers: Modifiers::default(),
+            }]
+        );
+    }
+
+    #[test]
+    fn scroll() {
+        let mut converter = InputConverter::new(Vector2::new(100.0, 100.0));
+        converter.set_scroll_line_size(10.0);
+        let device_id = unsafe { DeviceId::dummy() };
+        #[allow(deprecated)]
+        let wheel = |delta| WindowEvent::MouseWheel {
+            device_id,
+            delta,
+            phase: TouchPhase::Moved,
+            modifiers: Default::default(),
+        };
+        assert_eq!(
+            translate(&mut converter, wheel(MouseScrollDelta::LineDelta(0.0, 1.0)), CursorSpace::Pixels),
+            vec![InputMessage::CursorScroll(Vector2::new(0.0, 10.0))]
+        );
+        assert_eq!(
+            translate(
+                &mut converter,
+                wheel(MouseScrollDelta::PixelDelta(PhysicalPosition::new(-2.0, 3.5))),
+                CursorSpace::Pixels
+            ),
+            vec![InputMessage::CursorScroll(Vector2::new(-2.0, 3.5))]
+        );
+        assert_eq!(
+            translate(&mut converter, WindowEvent::CloseRequested, CursorSpace::Pixels),
+            vec![InputMessage::CloseRequested]
+        );
+    }
 }
@@ -0,0 +1,192 @@
+use crate::input::{CursorButton, InputMessage};
+use cgmath::prelude::*;
+use cgmath::*;
+
+/// A drag gesture event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DragEvent {
+    /// The cursor moved past the threshold while the button was held. Contains the position the
+    /// button was pressed at.
+    Started(Vector2<f32>),
+    /// The cursor moved while dragging.
+    Dragging {
+        /// Position the button was pressed at.
+        start: Vector2<f32>,
+        /// Current cursor position.
+        current: Vector2<f32>,
+        /// Change from the last drag position.
+        delta: Vector2<f32>,
+    },
+    /// The button was released while dragging.
+    Ended {
+        /// Position the button was pressed at.
+        start: Vector2<f32>,
+        /// Position the button was released at.
+        end: Vector2<f32>,
+    },
+}
+
+/// Recognizes click-and-drag gestures from input messages. A drag starts once the cursor moves
+/// further than the threshold from where the button was pressed, so small jitters during a click
+/// don't start a drag.
+pub struct DragRecognizer {
+    button: CursorButton,
+    threshold: f32,
+    start: Option<Vector2<f32>>,
+    last: Vector2<f32>,
+    dragging: bool,
+}
+
+impl DragRecognizer {
+    /// Creates a new recognizer for the given button. The threshold is in the same units as the
+    /// cursor positions being pushed.
+    pub fn new(button: CursorButton, threshold: f32) -> DragRecognizer {
+        DragRecognizer {
+            button,
+            threshold,
+            start: None,
+            last: Vector2::zero(),
+            dragging: false,
+        }
+    }
+
+    /// If a drag is currently in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Processes an input message, returning a drag event if one was recognized.
+    pub fn push(&mut self, message: &InputMessage) -> Option<DragEvent> {
+        match *message {
+            InputMessage::CursorPressed {
+                button,
+                pos,
+            } if button == self.button => {
+                self.start = Some(pos);
+                self.last = pos;
+                self.dragging = false;
+                None
+            }
+            InputMessage::CursorMoved {
+                pos,
+                ..
+            } => {
+                let start = self.start?;
+                if self.dragging {
+                    let delta = pos - self.last;
+                    self.last = pos;
+                    Some(DragEvent::Dragging {
+                        start,
+                        current: pos,
+                        delta,
+                    })
+                } else if (pos - start).magnitude() > self.threshold {
+                    self.dragging = true;
+                    Some(DragEvent::Started(start))
+                } else {
+                    None
+                }
+            }
+            InputMessage::CursorReleased {
+                button,
+                pos,
+            } if button == self.button => {
+                let start = self.start.take()?;
+                let dragging = self.dragging;
+                self.dragging = false;
+                if dragging {
+                    Some(DragEvent::Ended {
+                        start,
+                        end: pos,
+                    })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pressed(x: f32) -> InputMessage {
+        InputMessage::CursorPressed {
+            button: CursorButton::Left,
+            pos: Vector2::new(x, 0.0),
+        }
+    }
+
+    fn moved(x: f32) -> InputMessage {
+        InputMessage::CursorMoved {
+            pos: Vector2::new(x, 0.0),
+            delta: Vector2::zero(),
+        }
+    }
+
+    fn released(x: f32) -> InputMessage {
+        InputMessage::CursorReleased {
+            button: CursorButton::Left,
+            pos: Vector2::new(x, 0.0),
+        }
+    }
+
+    #[test]
+    fn drag() {
+        let mut drag = DragRecognizer::new(CursorButton::Left, 4.0);
+        let at = |x| Vector2::new(x, 0.0);
+
+        // Moving without a press, or within the threshold, doesn't start a drag.
+        assert_eq!(drag.push(&moved(20.0)), None);
+        assert_eq!(drag.push(&pressed(10.0)), None);
+        assert_eq!(drag.push(&moved(14.0)), None);
+        assert!(!drag.is_dragging());
+
+        assert_eq!(drag.push(&moved(15.0)), Some(DragEvent::Started(at(10.0))));
+        assert!(drag.is_dragging());
+        assert_eq!(
+            drag.push(&moved(18.0)),
+            Some(DragEvent::Dragging {
+                start: at(10.0),
+                current: at(18.0),
+                delta: at(8.0),
+            })
+        );
+        assert_eq!(
+            drag.push(&moved(17.0)),
+            Some(DragEvent::Dragging {
+                start: at(10.0),
+                current: at(17.0),
+                delta: at(-1.0),
+            })
+        );
+
+        // Other buttons are ignored, and releasing the button ends the drag.
+        let right = InputMessage::CursorReleased {
+            button: CursorButton::Right,
+            pos: at(17.0),
+        };
+        assert_eq!(drag.push(&right), None);
+        assert_eq!(
+            drag.push(&released(17.0)),
+            Some(DragEvent::Ended {
+                start: at(10.0),
+                end: at(17.0),
+            })
+        );
+        assert!(!drag.is_dragging());
+        assert_eq!(drag.push(&moved(30.0)), None);
+
+        // Releasing before passing the threshold cancels the drag without an event.
+        assert_eq!(drag.push(&pressed(0.0)), None);
+        assert_eq!(drag.push(&moved(3.0)), None);
+        assert_eq!(drag.push(&released(3.0)), None);
+        assert_eq!(drag.push(&moved(30.0)), None);
+    }
+}
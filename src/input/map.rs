@@ -0,0 +1,242 @@
+use crate::input::{CursorButton, InputMessage, InputState, KeyboardButton};
+use core::hash::Hash;
+use hashbrown::HashMap;
+
+/// A physical input that can be bound to an action.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum InputBinding {
+    /// A key on the keyboard.
+    Key(KeyboardButton),
+    /// A button on the mouse.
+    Cursor(CursorButton),
+}
+
+impl From<KeyboardButton> for InputBinding {
+    fn from(key: KeyboardButton) -> InputBinding {
+        InputBinding::Key(key)
+    }
+}
+
+impl From<CursorButton> for InputBinding {
+    fn from(button: CursorButton) -> InputBinding {
+        InputBinding::Cursor(button)
+    }
+}
+
+struct Action {
+    bindings: Vec<InputBinding>,
+    active: bool,
+    pressed: bool,
+    released: bool,
+}
+
+/// Maps named actions to the keys and cursor buttons bound to them, so gameplay code can check
+/// actions instead of specific keys. Actions can be any small copyable type, such as a string or a
+/// user defined enum. An action is active while any of its bindings is held, and bindings can be
+/// changed at any time to support rebinding.
+///
+/// Edge queries like `just_pressed` cover the input pushed since the previous
+/// `InputMessage::Update`. Pushing an update clears them, so check them while handling the update
+/// before pushing it to the map.
+pub struct InputMap<A: Copy + Eq + Hash> {
+    actions: HashMap<A, Action>,
+    state: InputState,
+}
+
+impl<A: Copy + Eq + Hash> Default for InputMap<A> {
+    fn default() -> InputMap<A> {
+        InputMap::new()
+    }
+}
+
+impl<A: Copy + Eq + Hash> InputMap<A> {
+    /// Creates a new map with no actions.
+    pub fn new() -> InputMap<A> {
+        InputMap {
+            actions: HashMap::new(),
+            state: InputState::new(),
+        }
+    }
+
+    /// Binds an input to the action. An action can have any number of bindings, and binding the
+    /// same input twice has no effect.
+    pub fn bind<B: Into<InputBinding>>(&mut self, action: A, binding: B) {
+        let binding = binding.into();
+        let action = self.actions.entry(action).or_insert_with(|| Action {
+            bindings: Vec::new(),
+            active: false,
+            pressed: false,
+            released: false,
+        });
+        if !action.bindings.contains(&binding) {
+            action.bindings.push(binding);
+            action.active = Self::is_active(&self.state, action);
+        }
+    }
+
+    /// Removes an input from the action's bindings.
+    pub fn unbind<B: Into<InputBinding>>(&mut self, action: A, binding: B) {
+        let binding = binding.into();
+        if let Some(action) = self.actions.get_mut(&action) {
+            action.bindings.retain(|&bound| bound != binding);
+            action.active = Self::is_active(&self.state, action);
+        }
+    }
+
+    /// Removes every binding from the action.
+    pub fn clear_bindings(&mut self, action: A) {
+        if let Some(action) = self.actions.get_mut(&action) {
+            action.bindings.clear();
+            action.active = false;
+        }
+    }
+
+    /// Gets the inputs bound to the action.
+    pub fn bindings(&self, action: A) -> &[InputBinding] {
+        match self.actions.get(&action) {
+            Some(action) => &action.bindings,
+            None => &[],
+        }
+    }
+
+    /// If any input bound to the action is currently held.
+    pub fn is_action_active(&self, action: A) -> bool {
+        matches!(self.actions.get(&action), Some(action) if action.active)
+    }
+
+    /// If the action became active since the previous update.
+    pub fn just_pressed(&self, action: A) -> bool {
+        matches!(self.actions.get(&action), Some(action) if action.pressed)
+    }
+
+    /// If the action stopped being active since the previous update.
+    pub fn just_released(&self, action: A) -> bool {
+        matches!(self.actions.get(&action), Some(action) if action.released)
+    }
+
+    /// Processes an input message, updating which actions are active.
+    pub fn push(&mut self, message: &InputMessage) {
+        let binding = match *message {
+            InputMessage::Update(_) => {
+                for action in self.actions.values_mut() {
+                    action.pressed = false;
+                    action.released = false;
+                }
+                return;
+            }
+            InputMessage::KeyPressed {
+                key,
+                ..
+            }
+            | InputMessage::KeyReleased {
+                key,
+                ..
+            } => InputBinding::Key(key),
+            InputMessage::CursorPressed {
+                button,
+                ..
+            }
+            | InputMessage::CursorReleased {
+                button,
+                ..
+            } => InputBinding::Cursor(button),
+            _ => return,
+        };
+
+        self.state.push(message);
+        for action in self.actions.values_mut() {
+            if action.bindings.contains(&binding) {
+                let active = Self::is_active(&self.state, action);
+                if active != action.active {
+                    action.active = active;
+                    if active {
+                        action.pressed = true;
+                    } else {
+                        action.released = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_active(state: &InputState, action: &Action) -> bool {
+        action.bindings.iter().any(|&binding| match binding {
+            InputBinding::Key(key) => state.is_key_down(key),
+            InputBinding::Cursor(button) => state.is_cursor_button_down(button),
+        })
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pressed(key: KeyboardButton) -> InputMessage {
+        InputMessage::KeyPressed {
+            key,
+            modifiers: Default::default(),
+        }
+    }
+
+    fn released(key: KeyboardButton) -> InputMessage {
+        InputMessage::KeyReleased {
+            key,
+            modifiers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn actions() {
+        let mut map = InputMap::new();
+        map.bind("jump", KeyboardButton::Space);
+        map.bind("jump", KeyboardButton::W);
+        map.bind("fire", CursorButton::Left);
+
+        map.push(&pressed(KeyboardButton::Space));
+        assert!(map.is_action_active("jump"));
+        assert!(map.just_pressed("jump"));
+        assert!(!map.is_action_active("fire"));
+        map.push(&InputMessage::Update(0.016));
+        assert!(!map.just_pressed("jump"));
+        assert!(map.is_action_active("jump"));
+
+        // A second binding held at the same time doesn't press the action again.
+        map.push(&pressed(KeyboardButton::W));
+        assert!(!map.just_pressed("jump"));
+        map.push(&released(KeyboardButton::Space));
+        assert!(map.is_action_active("jump"));
+        map.push(&released(KeyboardButton::W));
+        assert!(!map.is_action_active("jump"));
+        assert!(map.just_released("jump"));
+        map.push(&InputMessage::Update(0.016));
+
+        // Rebinding takes effect immediately.
+        map.unbind("jump", KeyboardButton::Space);
+        map.push(&pressed(KeyboardButton::Space));
+        assert!(!map.is_action_active("jump"));
+        assert!(!map.just_pressed("jump"));
+        assert_eq!(map.bindings("jump"), &[InputBinding::Key(KeyboardButton::W)]);
+    }
+
+    #[test]
+    fn updates_without_input() {
+        let mut map = InputMap::new();
+        map.bind("jump", KeyboardButton::Space);
+
+        map.push(&pressed(KeyboardButton::Space));
+        map.push(&InputMessage::Update(0.016));
+        map.push(&InputMessage::Update(0.016));
+        assert!(!map.just_pressed("jump"));
+        assert!(map.is_action_active("jump"));
+
+        map.push(&released(KeyboardButton::Space));
+        assert!(map.just_released("jump"));
+        map.push(&InputMessage::Update(0.016));
+        assert!(!map.just_released("jump"));
+        assert!(!map.just_pressed("jump"));
+    }
+}
@@ -1,21 +1,32 @@
+use crate::input::{CursorButton, KeyboardButton, Modifiers};
 use cgmath::*;
 
-// Re-exports.
-pub use winit::event::MouseButton as CursorButton;
-pub use winit::event::VirtualKeyCode as KeyboardButton;
-
 /// An input event. These are represented as an enumeration to preserve
 /// ordering when stored in a vector and read sequentially.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum InputMessage {
     /// The window has requested it close.
     CloseRequested,
-    /// Received a character.
+    /// Received a character. This is the character produced by the keyboard layout, including
+    /// dead keys, so it's what text input should use rather than KeyPressed. Control characters
+    /// such as backspace ('\u{8}') are passed through. A key that produces a character also
+    /// sends KeyPressed, and on most platforms the KeyPressed is sent first, but the order isn't
+    /// guaranteed, so the two shouldn't be paired up by order.
     ReceivedCharacter(char),
-    /// Keyboard press event.
-    KeyPressed(KeyboardButton),
-    /// Keyboard release event.
-    KeyReleased(KeyboardButton),
+    /// Keyboard press event. Contains the key pressed and the modifier keys held at the time.
+    KeyPressed {
+        /// Key pressed.
+        key: KeyboardButton,
+        /// Modifier keys held at time of press.
+        modifiers: Modifiers,
+    },
+    /// Keyboard release event. Contains the key released and the modifier keys held at the time.
+    KeyReleased {
+        /// Key released.
+        key: KeyboardButton,
+        /// Modifier keys held at time of release.
+        modifiers: Modifiers,
+    },
     /// Cursor press event. Contains the button pressed and the position it was pressed at.
     CursorPressed {
         /// Button pressed.
@@ -30,10 +41,15 @@ pub enum InputMessage {
         /// Cursor position at time of release.
         pos: Vector2<f32>,
     },
-    /// Cursor wheel scroll event.
-    CursorScroll(ScrollDirection),
+    /// Cursor wheel scroll event. Contains the distance scrolled in pixels, with positive Y for
+    /// scrolling up and positive X for scrolling right. Wheels that scroll in lines are converted
+    /// into pixels using the engine's scroll line size, so they share units with trackpads. This is
+    /// sent for every scroll the window reports, deltas aren't accumulated or combined.
+    CursorScroll(Vector2<f32>),
     /// Cursor moved event. Contains the position of the cursor and the delta from its last
-    /// position.
+    /// position. This is sent for every movement the window reports, moves aren't combined. The
+    /// position uses the same coordinates as the position in CursorPressed and CursorReleased. The
+    /// delta is zero for the first move after the cursor re-enters the window.
     CursorMoved {
         /// Current cursor position.
         pos: Vector2<f32>,
@@ -44,24 +60,45 @@ pub enum InputMessage {
     CursorLeft,
     /// Cursor entered the bounds of the window event.
     CursorEntered,
-    /// Window resized event. Contains the new dimensions of the window.
-    WindowResized(Vector2<f32>),
+    /// Window resized event. Every resize the window reports is sent, resizes aren't combined.
+    WindowResized {
+        /// New inner width of the window in physical pixels.
+        width: u32,
+        /// New inner height of the window in physical pixels.
+        height: u32,
+    },
+    /// The window gained or lost focus. Contains true if the window is now focused.
+    WindowFocused(bool),
+    /// The scale factor of the window changed, such as when it's moved to a monitor with a
+    /// different DPI. Contains the new number of physical pixels per logical pixel.
+    ScaleFactorChanged(f32),
     /// This event is useful as a place to put your code that should be run after all state-changing
     /// events have been handled and you want to do stuff (updating state, performing calculations,
     /// etc) that happens as the "main body" of your event loop. The value is the time passed since
     /// the last update in seconds.
     Update(f32),
+    /// Sent at a fixed rate once enabled with `Engine::fixed_update`, for simulation that needs a
+    /// constant step such as physics. Each frame sends zero or more of these before the Update for
+    /// that frame, enough to catch up to the current time. The value is the length of the step in
+    /// seconds.
+    FixedUpdate(f32),
+}
+
+/// When input messages are sent relative to a frame's fixed updates.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputOrder {
+    /// Input is sent before the fixed updates, so every fixed update sees it. This is the default.
+    BeforeFixedUpdate,
+    /// Input is sent after the fixed updates, so only the variable update sees it during the frame
+    /// it arrives in. The fixed updates see it on the next frame.
+    AfterFixedUpdate,
 }
 
-/// A cursor wheel movement. Some mice have left and right scroll options.
+/// The coordinate space cursor positions are reported in.
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub enum ScrollDirection {
-    /// Cursor wheel scrolled up.
-    Up,
-    /// Cursor wheel scrolled down.
-    Down,
-    /// Cursor wheel scrolled left.
-    Left,
-    /// Cursor wheel scrolled right.
-    Right,
+pub enum CursorSpace {
+    /// Pixels from the center of the window, with Y pointing up. This is the default.
+    Pixels,
+    /// Normalized device coordinates, ranging from -1 to 1 across the window with Y pointing up.
+    Normalized,
 }
@@ -1,5 +1,19 @@
+mod button;
+mod chord;
+mod click;
 mod converter;
+mod drag;
+mod map;
 mod message;
+mod names;
+mod state;
 
+pub use self::button::*;
+pub use self::chord::*;
+pub use self::click::*;
 pub use self::converter::*;
+pub use self::drag::*;
+pub use self::map::*;
 pub use self::message::*;
+pub use self::names::*;
+pub use self::state::*;
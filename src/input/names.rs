@@ -0,0 +1,205 @@
+use crate::input::KeyboardButton;
+
+/// Gets the display name of a key. This is intended for controls screens and for serializing
+/// bindings. The name can be turned back into the key with `key_from_name`.
+pub fn key_name(key: KeyboardButton) -> &'static str {
+    match KEY_NAMES.iter().find(|(other, _)| *other == key) {
+        Some((_, name)) => name,
+        None => "Unknown",
+    }
+}
+
+/// Parses the display name of a key as produced by `key_name`. Matching ignores ASCII case.
+/// Returns None if the name doesn't belong to any key.
+pub fn key_from_name(name: &str) -> Option<KeyboardButton> {
+    KEY_NAMES.iter().find(|(_, other)| other.eq_ignore_ascii_case(name)).map(|(key, _)| *key)
+}
+
+static KEY_NAMES: [(KeyboardButton, &str); 163] = [
+    (KeyboardButton::Key1, "1"),
+    (KeyboardButton::Key2, "2"),
+    (KeyboardButton::Key3, "3"),
+    (KeyboardButton::Key4, "4"),
+    (KeyboardButton::Key5, "5"),
+    (KeyboardButton::Key6, "6"),
+    (KeyboardButton::Key7, "7"),
+    (KeyboardButton::Key8, "8"),
+    (KeyboardButton::Key9, "9"),
+    (KeyboardButton::Key0, "0"),
+    (KeyboardButton::A, "A"),
+    (KeyboardButton::B, "B"),
+    (KeyboardButton::C, "C"),
+    (KeyboardButton::D, "D"),
+    (KeyboardButton::E, "E"),
+    (KeyboardButton::F, "F"),
+    (KeyboardButton::G, "G"),
+    (KeyboardButton::H, "H"),
+    (KeyboardButton::I, "I"),
+    (KeyboardButton::J, "J"),
+    (KeyboardButton::K, "K"),
+    (KeyboardButton::L, "L"),
+    (KeyboardButton::M, "M"),
+    (KeyboardButton::N, "N"),
+    (KeyboardButton::O, "O"),
+    (KeyboardButton::P, "P"),
+    (KeyboardButton::Q, "Q"),
+    (KeyboardButton::R, "R"),
+    (KeyboardButton::S, "S"),
+    (KeyboardButton::T, "T"),
+    (KeyboardButton::U, "U"),
+    (KeyboardButton::V, "V"),
+    (KeyboardButton::W, "W"),
+    (KeyboardButton::X, "X"),
+    (KeyboardButton::Y, "Y"),
+    (KeyboardButton::Z, "Z"),
+    (KeyboardButton::Escape, "Escape"),
+    (KeyboardButton::F1, "F1"),
+    (KeyboardButton::F2, "F2"),
+    (KeyboardButton::F3, "F3"),
+    (KeyboardButton::F4, "F4"),
+    (KeyboardButton::F5, "F5"),
+    (KeyboardButton::F6, "F6"),
+    (KeyboardButton::F7, "F7"),
+    (KeyboardButton::F8, "F8"),
+    (KeyboardButton::F9, "F9"),
+    (KeyboardButton::F10, "F10"),
+    (KeyboardButton::F11, "F11"),
+    (KeyboardButton::F12, "F12"),
+    (KeyboardButton::F13, "F13"),
+    (KeyboardButton::F14, "F14"),
+    (KeyboardButton::F15, "F15"),
+    (KeyboardButton::F16, "F16"),
+    (KeyboardButton::F17, "F17"),
+    (KeyboardButton::F18, "F18"),
+    (KeyboardButton::F19, "F19"),
+    (KeyboardButton::F20, "F20"),
+    (KeyboardButton::F21, "F21"),
+    (KeyboardButton::F22, "F22"),
+    (KeyboardButton::F23, "F23"),
+    (KeyboardButton::F24, "F24"),
+    (KeyboardButton::Snapshot, "Print Screen"),
+    (KeyboardButton::Scroll, "Scroll Lock"),
+    (KeyboardButton::Pause, "Pause"),
+    (KeyboardButton::Insert, "Insert"),
+    (KeyboardButton::Home, "Home"),
+    (KeyboardButton::Delete, "Delete"),
+    (KeyboardButton::End, "End"),
+    (KeyboardButton::PageDown, "Page Down"),
+    (KeyboardButton::PageUp, "Page Up"),
+    (KeyboardButton::Left, "Left Arrow"),
+    (KeyboardButton::Up, "Up Arrow"),
+    (KeyboardButton::Right, "Right Arrow"),
+    (KeyboardButton::Down, "Down Arrow"),
+    (KeyboardButton::Back, "Backspace"),
+    (KeyboardButton::Return, "Enter"),
+    (KeyboardButton::Space, "Space"),
+    (KeyboardButton::Compose, "Compose"),
+    (KeyboardButton::Caret, "Caret"),
+    (KeyboardButton::Numlock, "Num Lock"),
+    (KeyboardButton::Numpad0, "Numpad 0"),
+    (KeyboardButton::Numpad1, "Numpad 1"),
+    (KeyboardButton::Numpad2, "Numpad 2"),
+    (KeyboardButton::Numpad3, "Numpad 3"),
+    (KeyboardButton::Numpad4, "Numpad 4"),
+    (KeyboardButton::Numpad5, "Numpad 5"),
+    (KeyboardButton::Numpad6, "Numpad 6"),
+    (KeyboardButton::Numpad7, "Numpad 7"),
+    (KeyboardButton::Numpad8, "Numpad 8"),
+    (KeyboardButton::Numpad9, "Numpad 9"),
+    (KeyboardButton::NumpadAdd, "Numpad Add"),
+    (KeyboardButton::NumpadDivide, "Numpad Divide"),
+    (KeyboardButton::NumpadDecimal, "Numpad Decimal"),
+    (KeyboardButton::NumpadComma, "Numpad Comma"),
+    (KeyboardButton::NumpadEnter, "Numpad Enter"),
+    (KeyboardButton::NumpadEquals, "Numpad Equals"),
+    (KeyboardButton::NumpadMultiply, "Numpad Multiply"),
+    (KeyboardButton::NumpadSubtract, "Numpad Subtract"),
+    (KeyboardButton::AbntC1, "Abnt C1"),
+    (KeyboardButton::AbntC2, "Abnt C2"),
+    (KeyboardButton::Apostrophe, "Apostrophe"),
+    (KeyboardButton::Apps, "Apps"),
+    (KeyboardButton::Asterisk, "Asterisk"),
+    (KeyboardButton::At, "At"),
+    (KeyboardButton::Ax, "Ax"),
+    (KeyboardButton::Backslash, "Backslash"),
+    (KeyboardButton::Calculator, "Calculator"),
+    (KeyboardButton::Capital, "Caps Lock"),
+    (KeyboardButton::Colon, "Colon"),
+    (KeyboardButton::Comma, "Comma"),
+    (KeyboardButton::Convert, "Convert"),
+    (KeyboardButton::Equals, "Equals"),
+    (KeyboardButton::Grave, "Grave"),
+    (KeyboardButton::Kana, "Kana"),
+    (KeyboardButton::Kanji, "Kanji"),
+    (KeyboardButton::LAlt, "Left Alt"),
+    (KeyboardButton::LBracket, "Left Bracket"),
+    (KeyboardButton::LControl, "Left Control"),
+    (KeyboardButton::LShift, "Left Shift"),
+    (KeyboardButton::LWin, "Left Windows"),
+    (KeyboardButton::Mail, "Mail"),
+    (KeyboardButton::MediaSelect, "Media Select"),
+    (KeyboardButton::MediaStop, "Media Stop"),
+    (KeyboardButton::Minus, "Minus"),
+    (KeyboardButton::Mute, "Mute"),
+    (KeyboardButton::MyComputer, "My Computer"),
+    (KeyboardButton::NavigateForward, "Navigate Forward"),
+    (KeyboardButton::NavigateBackward, "Navigate Backward"),
+    (KeyboardButton::NextTrack, "Next Track"),
+    (KeyboardButton::NoConvert, "No Convert"),
+    (KeyboardButton::OEM102, "OEM 102"),
+    (KeyboardButton::Period, "Period"),
+    (KeyboardButton::PlayPause, "Play Pause"),
+    (KeyboardButton::Plus, "Plus"),
+    (KeyboardButton::Power, "Power"),
+    (KeyboardButton::PrevTrack, "Previous Track"),
+    (KeyboardButton::RAlt, "Right Alt"),
+    (KeyboardButton::RBracket, "Right Bracket"),
+    (KeyboardButton::RControl, "Right Control"),
+    (KeyboardButton::RShift, "Right Shift"),
+    (KeyboardButton::RWin, "Right Windows"),
+    (KeyboardButton::Semicolon, "Semicolon"),
+    (KeyboardButton::Slash, "Slash"),
+    (KeyboardButton::Sleep, "Sleep"),
+    (KeyboardButton::Stop, "Stop"),
+    (KeyboardButton::Sysrq, "SysRq"),
+    (KeyboardButton::Tab, "Tab"),
+    (KeyboardButton::Underline, "Underline"),
+    (KeyboardButton::Unlabeled, "Unlabeled"),
+    (KeyboardButton::VolumeDown, "Volume Down"),
+    (KeyboardButton::VolumeUp, "Volume Up"),
+    (KeyboardButton::Wake, "Wake"),
+    (KeyboardButton::WebBack, "Web Back"),
+    (KeyboardButton::WebFavorites, "Web Favorites"),
+    (KeyboardButton::WebForward, "Web Forward"),
+    (KeyboardButton::WebHome, "Web Home"),
+    (KeyboardButton::WebRefresh, "Web Refresh"),
+    (KeyboardButton::WebSearch, "Web Search"),
+    (KeyboardButton::WebStop, "Web Stop"),
+    (KeyboardButton::Yen, "Yen"),
+    (KeyboardButton::Copy, "Copy"),
+    (KeyboardButton::Paste, "Paste"),
+    (KeyboardButton::Cut, "Cut"),
+];
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for (key, _) in KEY_NAMES.iter() {
+            assert_eq!(key_from_name(key_name(*key)), Some(*key));
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(key_from_name("left shift"), Some(KeyboardButton::LShift));
+        assert_eq!(key_from_name("Space"), Some(KeyboardButton::Space));
+        assert_eq!(key_from_name("Not A Key"), None);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::input::{CursorButton, InputMessage, KeyboardButton};
+use cgmath::prelude::*;
+use cgmath::*;
+use hashbrown::HashSet;
+
+/// A snapshot of which keys and cursor buttons are held, built from input messages. This is useful
+/// for continuous actions like movement, where checking if a key is down each update is simpler
+/// than tracking presses and releases. The engine keeps one up to date, see
+/// `Engine::input_state`.
+#[derive(Clone, Debug)]
+pub struct InputState {
+    keys: HashSet<KeyboardButton>,
+    buttons: HashSet<CursorButton>,
+    cursor_pos: Vector2<f32>,
+}
+
+impl Default for InputState {
+    fn default() -> InputState {
+        InputState::new()
+    }
+}
+
+impl InputState {
+    /// Creates a new state with nothing held and the cursor at zero.
+    pub fn new() -> InputState {
+        InputState {
+            keys: HashSet::new(),
+            buttons: HashSet::new(),
+            cursor_pos: Vector2::zero(),
+        }
+    }
+
+    /// If the key is currently held.
+    pub fn is_key_down(&self, key: KeyboardButton) -> bool {
+        self.keys.contains(&key)
+    }
+
+    /// If the cursor button is currently held.
+    pub fn is_cursor_button_down(&self, button: CursorButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    /// The last reported position of the cursor, in the cursor space the messages were reported in.
+    pub fn cursor_position(&self) -> Vector2<f32> {
+        self.cursor_pos
+    }
+
+    /// Updates the state from an input message. Messages that don't affect the state are ignored.
+    pub fn push(&mut self, message: &InputMessage) {
+        match *message {
+            InputMessage::KeyPressed {
+                key,
+                ..
+            } => {
+                self.keys.insert(key);
+            }
+            InputMessage::KeyReleased {
+                key,
+                ..
+            } => {
+                self.keys.remove(&key);
+            }
+            InputMessage::CursorPressed {
+                button,
+                pos,
+            } => {
+                self.buttons.insert(button);
+                self.cursor_pos = pos;
+            }
+            InputMessage::CursorReleased {
+                button,
+                pos,
+            } => {
+                self.buttons.remove(&button);
+                self.cursor_pos = pos;
+            }
+            InputMessage::CursorMoved {
+                pos,
+                ..
+            } => {
+                self.cursor_pos = pos;
+            }
+            _ => {}
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state() {
+        let mut state = InputState::new();
+        let key = |key| InputMessage::KeyPressed {
+            key,
+            modifiers: Default::default(),
+        };
+        state.push(&key(KeyboardButton::W));
+        state.push(&InputMessage::CursorPressed {
+            button: CursorButton::Left,
+            pos: Vector2::new(1.0, 2.0),
+        });
+        assert!(state.is_key_down(KeyboardButton::W));
+        assert!(!state.is_key_down(KeyboardButton::S));
+        assert!(state.is_cursor_button_down(CursorButton::Left));
+        assert_eq!(state.cursor_position(), Vector2::new(1.0, 2.0));
+
+        // A press and release between queries leaves the key up.
+        state.push(&key(KeyboardButton::S));
+        state.push(&InputMessage::KeyReleased {
+            key: KeyboardButton::S,
+            modifiers: Default::default(),
+        });
+        assert!(!state.is_key_down(KeyboardButton::S));
+
+        state.push(&InputMessage::CursorMoved {
+            pos: Vector2::new(3.0, 4.0),
+            delta: Vector2::new(2.0, 2.0),
+        });
+        state.push(&InputMessage::CursorReleased {
+            button: CursorButton::Left,
+            pos: Vector2::new(3.0, 4.0),
+        });
+        assert!(!state.is_cursor_button_down(CursorButton::Left));
+        assert_eq!(state.cursor_position(), Vector2::new(3.0, 4.0));
+    }
+}
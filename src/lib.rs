@@ -5,59 +5,125 @@ pub extern crate log;
 extern crate alloc;
 
 pub mod math;
+pub mod physics;
 pub mod time;
+pub mod utility;
 
+pub use crate::gui::*;
 pub use crate::input::*;
-pub use crate::render::{ClearMode, Layer};
+pub use crate::render::{
+    ClearMode, Framebuffer, Layer, Lighting, PointLight, Shader, ShapeRenderer, Tilemap,
+};
 pub use crate::types::*;
 pub use cgmath;
 
+mod gui;
 mod input;
 mod render;
 mod text;
 mod texture;
 mod types;
-mod utility;
 
-use crate::input::InputConverter;
+use crate::math::AABB2D;
 use crate::render::Renderer;
-use crate::time::{Instant, Timer};
+use crate::time::{Clock, FixedTimestep, Instant, Timer};
+use core::any::Any;
 use core::time::Duration;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use winit::event::Event;
 use winit::event_loop::ControlFlow;
 
+/// A function called with the message of a caught panic, see `Engine::on_panic`.
+pub type PanicHandler = Box<dyn FnMut(&str, &mut Engine)>;
+
 /// The main entry point into the Storm engine. All interactions with the engine are managed by the
 /// API on this type. The engine is send, and can be moved between threads.
+///
+/// # Frame order
+///
+/// Every frame runs in the same order on the thread that started the engine:
+///
+/// 1. Every pending window event is read from the operating system and converted into input
+///    messages, which are queued in the order they happened.
+/// 2. If the frame isn't skipped by `wait_periodic`, `wait_until`, or `pause_on_unfocus`, the
+///    queued input and any due `InputMessage::FixedUpdate` events are sent, in the order set by
+///    `input_order`. `InputMessage::Update` is sent last.
+/// 3. The window's buffers are swapped, presenting whatever was drawn during the update.
+///
+/// Input that arrives during a skipped frame is still sent at the end of the frame, and is seen by
+/// the next update. Input for a frame is always seen before that frame's update, and never during
+/// it.
 pub struct Engine {
     render: Renderer,
     stop: bool,
     control_flow: Option<ControlFlow>,
-    last_update: Instant,
     wait_next: Instant,
     wait_periodic: Option<Duration>,
+    catch_panics: bool,
+    panicked: bool,
+    panic_handler: Option<PanicHandler>,
+    cursor_space: CursorSpace,
+    input_order: InputOrder,
+    scroll_line_size: f32,
+    input_state: InputState,
+    pause_on_unfocus: bool,
+    focused: bool,
+    frame_budget: Option<Duration>,
+    clock: Clock,
+    fixed: Option<FixedTimestep>,
+    paused: bool,
 }
 
 impl Engine {
+    /// Starts the engine. If the window or its graphics context can't be created, this function
+    /// will panic.
     // 'static + FnMut(&mut Engine) -> FnMut(InputMessage, &mut Engine)
     pub fn start<T: 'static + FnMut(InputMessage, &mut Engine)>(
         desc: WindowSettings,
         event_handler_creator: fn(&mut Engine) -> T,
     ) {
+        if let Err(error) = Engine::try_start(desc, event_handler_creator) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Starts the engine. Once the window and its graphics context are created this function never
+    /// returns. If they can't be created, such as when the requested context attributes aren't
+    /// supported, an error describing the failure is returned instead.
+    pub fn try_start<T: 'static + FnMut(InputMessage, &mut Engine)>(
+        desc: WindowSettings,
+        event_handler_creator: fn(&mut Engine) -> T,
+    ) -> Result<(), ContextError> {
         info!("Starting engine");
         let event_loop = winit::event_loop::EventLoop::new();
-        let render = Renderer::new(&desc, &event_loop);
-        let mut input = InputConverter::new(render.window_logical_size());
+        let render = Renderer::new(&desc, &event_loop)?;
+        let mut input = InputConverter::new(render.window_physical_size());
         let mut engine = Engine {
             render,
             stop: false,
             control_flow: Some(ControlFlow::Poll),
-            last_update: Instant::now(),
             wait_next: Instant::now(),
             wait_periodic: None,
+            catch_panics: false,
+            panicked: false,
+            panic_handler: None,
+            cursor_space: CursorSpace::Pixels,
+            input_order: InputOrder::BeforeFixedUpdate,
+            scroll_line_size: 20.0,
+            input_state: InputState::new(),
+            pause_on_unfocus: false,
+            focused: true,
+            frame_budget: None,
+            clock: Clock::new(),
+            fixed: None,
+            paused: false,
         };
+        // The engine doesn't clamp the delta unless asked to, see `Engine::max_delta`.
+        engine.clock.set_max_delta(None);
         info!("Starting handler");
         let mut event_handler = event_handler_creator(&mut engine);
         let mut update_timer = Timer::new("InputMessage::Update");
+        let mut queue = Vec::new();
         info!("Starting loop");
         event_loop.run(move |event, _, control_flow| {
             match event {
@@ -65,22 +131,55 @@ impl Engine {
                     event,
                     ..
                 } => {
-                    input.push(event, &mut event_handler, &mut engine);
+                    engine.guard(|engine| input.push(event, engine, &mut queue));
                 }
                 Event::MainEventsCleared => {
                     let now = Instant::now();
-                    if now >= engine.wait_next {
+                    if engine.is_unfocused_paused() {
+                        // Time spent paused isn't included in the next update's delta.
+                        engine.clock.skip_to(now);
+                        if let Some(fixed) = &mut engine.fixed {
+                            fixed.tick(now);
+                        }
+                    } else if now >= engine.wait_next {
                         if let Some(duration) = engine.wait_periodic {
                             engine.wait_next = now + duration;
                             engine.control_flow = Some(ControlFlow::WaitUntil(engine.wait_next));
                         }
-                        let delta = (now - engine.last_update).as_secs_f32();
+                        let mut delta = engine.clock.tick_at(now);
+                        let (mut fixed_steps, fixed_step) = match &mut engine.fixed {
+                            Some(fixed) => (fixed.tick(now), fixed.step()),
+                            None => (0, 0.0),
+                        };
+                        if engine.paused {
+                            // Game time is frozen, but updates are still sent so the game can draw.
+                            delta = 0.0;
+                            fixed_steps = 0;
+                        }
                         update_timer.start();
-                        event_handler(InputMessage::Update(delta), &mut engine);
-                        engine.render.window_swap_buffers();
+                        engine.guard(|engine| {
+                            let order = engine.input_order;
+                            dispatch_frame(order, &mut queue, fixed_steps, fixed_step, delta, |message| {
+                                engine.input_state.push(&message);
+                                event_handler(message, engine);
+                            });
+                            engine.render.window_swap_buffers();
+                        });
                         update_timer.stop();
-                        engine.last_update = now;
+                        if let Some(budget) = engine.frame_budget {
+                            let frame_time = now.elapsed();
+                            if frame_time > budget {
+                                warn!("Frame exceeded budget: {:?} > {:?}", frame_time, budget);
+                            }
+                        }
                     }
+                    // Input that arrived during a skipped frame is still sent.
+                    engine.guard(|engine| {
+                        for message in queue.drain(..) {
+                            engine.input_state.push(&message);
+                            event_handler(message, engine);
+                        }
+                    });
                 }
                 Event::LoopDestroyed => {
                     info!("Stopped engine");
@@ -97,10 +196,51 @@ impl Engine {
         });
     }
 
+    /// Runs the function, stopping the engine if it panics and panics are being caught. The panic
+    /// handler is called with the panic's message before the engine stops.
+    fn guard<F: FnOnce(&mut Engine)>(&mut self, f: F) {
+        if self.panicked {
+            return;
+        }
+        if !self.catch_panics {
+            f(self);
+        } else if let Err(payload) = catch_unwind(AssertUnwindSafe(|| f(self))) {
+            error!("Event handler panicked, stopping engine");
+            self.panicked = true;
+            self.stop = true;
+            if let Some(mut handler) = self.panic_handler.take() {
+                let message = panic_message(&*payload);
+                if catch_unwind(AssertUnwindSafe(|| handler(message, self))).is_err() {
+                    error!("Panic handler panicked");
+                }
+            }
+        }
+    }
+
     pub(crate) fn window_check_resize(&mut self) {
         self.render.window_check_resize();
     }
 
+    pub(crate) fn window_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if self.pause_on_unfocus {
+            self.update_pause_control_flow();
+        }
+    }
+
+    fn is_unfocused_paused(&self) -> bool {
+        self.pause_on_unfocus && !self.focused
+    }
+
+    /// Sleeps until the next event while paused, and resumes polling otherwise.
+    fn update_pause_control_flow(&mut self) {
+        self.control_flow = Some(if self.is_unfocused_paused() {
+            ControlFlow::Wait
+        } else {
+            ControlFlow::Poll
+        });
+    }
+
     // ////////////////////////////////////////////////////////
     // Layer
     // ////////////////////////////////////////////////////////
@@ -111,6 +251,82 @@ impl Engine {
         self.render.layer_create()
     }
 
+    /// Creates a new layer with room reserved for at least the given number of sprites. This
+    /// avoids growing the layer's storage when it's first given a large number of sprites.
+    pub fn layer_create_with_capacity(&mut self, capacity: usize) -> Layer {
+        let mut layer = self.render.layer_create();
+        layer.reserve_sprites(capacity);
+        layer
+    }
+
+    // ////////////////////////////////////////////////////////
+    // Framebuffer
+    // ////////////////////////////////////////////////////////
+
+    /// Creates an offscreen framebuffer with a color texture of the given size in pixels. Draws
+    /// made between `framebuffer_begin` and `framebuffer_end` go to its texture instead of the
+    /// window, and layers can sample the result with `Layer::draw_sampling`. Returns an error with
+    /// the framebuffer status if the driver can't complete it, such as when the size is 0 or too
+    /// large.
+    pub fn framebuffer_create(&mut self, width: u32, height: u32) -> Result<Framebuffer, FramebufferError> {
+        self.render.framebuffer_create(width, height, false)
+    }
+
+    /// Creates an offscreen framebuffer like `framebuffer_create`, with a depth and stencil
+    /// attachment as well, so sprite depth and masks work while drawing into it.
+    pub fn framebuffer_create_with_depth(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> Result<Framebuffer, FramebufferError> {
+        self.render.framebuffer_create(width, height, true)
+    }
+
+    /// Starts drawing into the framebuffer. Clears and layer draws go to its attachments until
+    /// `framebuffer_end` is called. Framebuffers don't follow the window size, so resize them with
+    /// `Framebuffer::resize` when `InputMessage::WindowResized` arrives.
+    pub fn framebuffer_begin(&mut self, framebuffer: &Framebuffer) {
+        self.render.framebuffer_begin(framebuffer);
+    }
+
+    /// Stops drawing into a framebuffer, so draws go to the window again.
+    pub fn framebuffer_end(&mut self) {
+        self.render.framebuffer_end();
+    }
+
+    // ////////////////////////////////////////////////////////
+    // Lighting
+    // ////////////////////////////////////////////////////////
+
+    /// Creates a lighting pass with a light map the size of the window's physical size. Each frame,
+    /// call `Lighting::render` to draw the lights into the light map, then `Lighting::draw` after
+    /// the scene to light it. Returns an error with the framebuffer status if the driver can't
+    /// create the light map.
+    pub fn lighting_create(&mut self) -> Result<Lighting, FramebufferError> {
+        self.render.lighting_create()
+    }
+
+    // ////////////////////////////////////////////////////////
+    // Shader
+    // ////////////////////////////////////////////////////////
+
+    /// Compiles the vertex and fragment GLSL sources and links them into a shader program. The
+    /// error holds the graphics driver's info log when compiling or linking fails.
+    pub fn shader_create(&mut self, vertex: &str, fragment: &str) -> Result<Shader, ShaderError> {
+        self.render.shader_create(vertex, fragment)
+    }
+
+    /// Starts drawing layers with the shader instead of the built in sprite shader. See `Shader`
+    /// for the attributes and uniforms layers provide to it.
+    pub fn shader_begin(&mut self, shader: &Shader) {
+        self.render.shader_begin(shader);
+    }
+
+    /// Stops drawing with a custom shader, so layers draw with the built in sprite shader again.
+    pub fn shader_end(&mut self) {
+        self.render.shader_end();
+    }
+
     // ////////////////////////////////////////////////////////
     // String
     // ////////////////////////////////////////////////////////
@@ -122,15 +338,36 @@ impl Engine {
     }
 
     /// Rasterizes text into sprites. This function appends sprites to the end of the output buffer.
-    pub fn text_append(&mut self, descs: &Vec<Text>, output: &mut Vec<Sprite>) {
+    ///
+    /// Glyphs are cached in a fixed number of atlas pages. Once every page is full, the whole cache
+    /// is evicted at once rather than glyph by glyph, so sprites from earlier calls may reference
+    /// glyphs that have since been replaced. Rasterize text again whenever `text_generation`
+    /// changes.
+    pub fn text_append(&mut self, descs: &[Text], output: &mut Vec<Sprite>) {
         self.render.text_append(descs, output)
     }
 
     /// Rasterizes text into sprites. This function appends sprites to the end of the output buffer.
-    pub fn text_clear(&mut self, descs: &Vec<Text>, output: &mut Vec<Sprite>) {
+    pub fn text_clear(&mut self, descs: &[Text], output: &mut Vec<Sprite>) {
         self.render.text_clear(descs, output)
     }
 
+    /// Measures the bounds text would be drawn in, accounting for its position, alignment, and line
+    /// breaks. This doesn't rasterize any glyphs, so it's cheap to call before drawing, such as to
+    /// size a box around the text.
+    pub fn text_measure(&mut self, desc: &Text) -> AABB2D {
+        self.render.text_measure(desc)
+    }
+
+    /// Gets the generation of the glyph cache. Glyphs are cached in a limited region of the texture
+    /// atlas, and when it fills up every cached glyph is evicted and the generation increases.
+    /// Sprites rasterized from text in an earlier generation may show the wrong glyphs, so text
+    /// that's kept across frames should be rasterized again when this changes. Unsupported
+    /// characters are drawn as a box.
+    pub fn text_generation(&self) -> u64 {
+        self.render.text_generation()
+    }
+
     // ////////////////////////////////////////////////////////
     // Texture
     // ////////////////////////////////////////////////////////
@@ -138,6 +375,19 @@ impl Engine {
     /// Creates a new texture from bytes. If there is an issue loading the texture, this function
     /// will panic.
     pub fn texture_create(&mut self, bytes: &[u8], format: TextureFormat) -> Texture {
+        match self.render.texture_create(bytes, format) {
+            Ok(texture) => texture,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Creates a new texture from encoded bytes, such as a file embedded with include_bytes. The
+    /// format is detected from the header of the bytes.
+    ///
+    /// Returns an error if the format isn't supported, the bytes couldn't be decoded, or there isn't
+    /// room left in the texture atlas for it.
+    pub fn texture_from_bytes(&mut self, bytes: &[u8]) -> Result<Texture, TextureError> {
+        let format = TextureFormat::detect(bytes).ok_or(TextureError::UnsupportedFormat)?;
         self.render.texture_create(bytes, format)
     }
 
@@ -145,26 +395,134 @@ impl Engine {
     // Window
     // ////////////////////////////////////////////////////////
 
+    /// Gets the scale factor of the window, which is the number of physical pixels per logical
+    /// pixel. This is above 1 on HiDPI displays, and changes when the window moves to a monitor
+    /// with a different scale, which sends `InputMessage::ScaleFactorChanged`. Sizing UI in logical
+    /// units multiplied by this keeps it physically consistent across displays.
+    pub fn window_scale_factor(&self) -> f32 {
+        self.render.window_scale_factor()
+    }
+
+    /// Captures the pixels drawn so far this frame. This reads the buffer being drawn to, so calling
+    /// it at the end of an update, after everything has been drawn, captures that frame. Returns
+    /// the pixels as tightly packed RGBA with the top row first, followed by the width and height.
+    pub fn window_capture(&self) -> (Vec<u8>, u32, u32) {
+        self.render.window_capture()
+    }
+
+    /// Gets information about the graphics context that was created, such as its version.
+    pub fn window_context_info(&self) -> ContextInfo {
+        self.render.window_context_info()
+    }
+
     /// Sets the title of the window.
     pub fn window_title(&mut self, title: &str) {
         self.render.window_title(title);
     }
 
-    /// Sets the display mode of the window.
+    /// Sets the display mode of the window, such as switching between windowed and fullscreen. The
+    /// graphics context is kept, so layers and textures remain valid. Exclusive fullscreen can
+    /// target any monitor and video mode, see `DisplayMode::Fullscreen`. The new size is reported
+    /// with `InputMessage::WindowResized` once the window has changed.
     pub fn window_display_mode(&mut self, display_mode: DisplayMode) {
         self.render.window_display_mode(display_mode);
     }
 
-    /// Sets the clear color for the window.
+    /// Sets the icon of the cursor while it's over the window. The icon persists until it's changed
+    /// again, and CursorIcon::Default restores the regular arrow. Platforms missing an icon will
+    /// use the closest available one.
+    pub fn window_cursor_icon(&mut self, cursor_icon: CursorIcon) {
+        self.render.window_cursor_icon(cursor_icon);
+    }
+
+    /// Lists all the monitors attached to the system.
+    pub fn window_monitors(&self) -> Vec<MonitorInfo> {
+        self.render.window_monitors()
+    }
+
+    /// Gets the monitor the window is currently on. Returns None if it can't be determined.
+    pub fn window_current_monitor(&self) -> Option<MonitorInfo> {
+        self.render.window_current_monitor()
+    }
+
+    /// Lists the video modes supported by every monitor attached to the system.
+    pub fn window_video_modes(&self) -> Vec<VideoMode> {
+        self.render.window_video_modes()
+    }
+
+    /// Gets the refresh rate in hertz of the monitor the window is currently on. Returns None if
+    /// it can't be determined.
+    pub fn window_refresh_rate(&self) -> Option<u16> {
+        match self.render.window_current_monitor() {
+            Some(monitor) if monitor.refresh_rate > 0 => Some(monitor.refresh_rate),
+            _ => None,
+        }
+    }
+
+    /// Sets the color the color buffer is cleared to. The color is kept until it's set again, so
+    /// it only needs to be set when it changes. The default is opaque black.
     pub fn clear_color(&mut self, clear_color: RGBA8) {
         self.render.clear_color(clear_color);
     }
 
-    /// Clears the screen buffers according to the clear mode.
+    /// Clears the screen buffers according to the clear mode. The engine doesn't clear the screen
+    /// on its own, so this is usually called with `ClearMode::COLOR | ClearMode::DEPTH` at the start
+    /// of each update, before any layers are drawn.
     pub fn clear(&mut self, clear_mode: ClearMode) {
         self.render.clear(clear_mode);
     }
 
+    // ////////////////////////////////////////////////////////
+    // Mask
+    // ////////////////////////////////////////////////////////
+
+    /// Starts drawing a mask. Layers drawn after this don't appear on screen, and instead define
+    /// the shape of the mask with the opaque parts of their sprites. Any previous mask is cleared.
+    pub fn mask_begin(&mut self) {
+        self.render.mask_begin();
+    }
+
+    /// Finishes the mask started with `mask_begin`. Layers drawn after this only appear where the
+    /// mask was drawn.
+    pub fn mask_content(&mut self) {
+        self.render.mask_content();
+    }
+
+    /// Stops masking. Layers drawn after this appear everywhere again.
+    pub fn mask_end(&mut self) {
+        self.render.mask_end();
+    }
+
+    // ////////////////////////////////////////////////////////
+    // Input
+    // ////////////////////////////////////////////////////////
+
+    /// Sets the coordinate space cursor positions are reported in. Conversions use the current
+    /// size of the window. The default is CursorSpace::Pixels.
+    pub fn input_cursor_space(&mut self, cursor_space: CursorSpace) {
+        self.cursor_space = cursor_space;
+    }
+
+    /// Sets the number of pixels a scroll of one line is converted into. Mouse wheels usually
+    /// scroll in lines while trackpads scroll in pixels, and this puts them in the same units. The
+    /// default is 20.
+    pub fn input_scroll_line_size(&mut self, pixels: f32) {
+        self.scroll_line_size = pixels;
+    }
+
+    /// Sets whether input is sent before or after a frame's fixed updates. Either way, input is sent
+    /// before the frame's update. The default is InputOrder::BeforeFixedUpdate.
+    pub fn input_order(&mut self, order: InputOrder) {
+        self.input_order = order;
+    }
+
+    /// Gets which keys and cursor buttons are currently held. This is updated with each input
+    /// message before the message is sent to the event handler, so it already reflects the message
+    /// being handled.
+    pub fn input_state(&self) -> &InputState {
+        &self.input_state
+    }
+
     // ////////////////////////////////////////////////////////
     // Control
     // ////////////////////////////////////////////////////////
@@ -197,4 +555,206 @@ impl Engine {
     pub fn wait_periodic(&mut self, duration: Option<Duration>) {
         self.wait_periodic = duration;
     }
+
+    /// Sets the time budget for a frame, measured from the start of the update event through the
+    /// buffer swap. Frames that take longer log a warning with the measured frame time. A common
+    /// budget is the inverse of the target frame rate. Set this to None to disable the warning.
+    pub fn frame_budget(&mut self, budget: Option<Duration>) {
+        self.frame_budget = budget;
+    }
+
+    /// Sets the largest delta the update event receives. Longer frames, such as after a stall or
+    /// resuming from a breakpoint, report this delta instead so the game doesn't jump forward. The
+    /// smoothed frame rate still measures the real frame time. Fixed update events only catch up
+    /// to this much time as well. Set this to None to disable the clamp, which is the default.
+    pub fn max_delta(&mut self, max_delta: Option<Duration>) {
+        let max_delta = max_delta.map(|max_delta| max_delta.as_secs_f32());
+        self.clock.set_max_delta(max_delta);
+        if let Some(fixed) = &mut self.fixed {
+            fixed.set_max_delta(max_delta);
+        }
+    }
+
+    /// Gets the number of update events that had their delta clamped by the max delta.
+    pub fn clamped_frames(&self) -> u64 {
+        self.clock.clamped_frames()
+    }
+
+    /// Gets the number of draw calls made during the previous frame. Each layer with sprites draws
+    /// all of them in a single call, so this is useful for checking how many layers are in use.
+    pub fn draw_calls(&self) -> u32 {
+        self.render.draw_calls()
+    }
+
+    /// Gets the frame rate, smoothed over recent frames so it's readable when displayed. This is
+    /// updated before each update event.
+    pub fn smoothed_fps(&self) -> f32 {
+        self.clock.smoothed_fps()
+    }
+
+    /// Gets the clock measuring time between update events. It keeps the durations of the last 60
+    /// frames, and is updated before each update event.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Sets how much each new frame affects the smoothed frame rate, from 0 to 1. Smaller values
+    /// are steadier but slower to react to changes. The default is 0.1.
+    pub fn fps_smoothing(&mut self, smoothing: f32) {
+        self.clock.set_smoothing(smoothing);
+    }
+
+    /// Enables the fixed update event at the given number of steps per second. Each frame, enough
+    /// fixed update events are sent before the update event to catch up to the current time, which
+    /// may be none or several. After a stall, at most 8 steps are sent and the remaining time is
+    /// dropped, so a slow step can't cause every following frame to fall further behind. Time
+    /// beyond the max delta is dropped too, see `Engine::max_delta`. Set this to None to disable
+    /// the fixed update event, which is the default.
+    pub fn fixed_update(&mut self, hz: Option<f64>) {
+        let max_delta = self.clock.max_delta();
+        self.fixed = hz.map(|hz| {
+            let mut fixed = FixedTimestep::new(hz);
+            fixed.set_max_delta(max_delta);
+            fixed.tick(Instant::now());
+            fixed
+        });
+    }
+
+    /// Gets how far the current frame is between the last fixed update and the next, from 0 to 1.
+    /// Rendering can use this during the update event to interpolate between the previous and
+    /// current fixed states, see `math::lerp`. The value is clamped, and can be close to 1 when the
+    /// next fixed update is about to be sent. This is 0 when the fixed update event is disabled.
+    pub fn fixed_alpha(&self) -> f32 {
+        match &self.fixed {
+            Some(fixed) => fixed.alpha(),
+            None => 0.0,
+        }
+    }
+
+    /// Pauses game time. While paused, update events are still sent so menus can be drawn and
+    /// respond to input, but their delta is 0 and no fixed update events are sent. Anything
+    /// advanced by the update's delta, like a Stopwatch, stops with it. Time spent paused isn't
+    /// caught up after resuming.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes game time after a pause. The first update after resuming has a normal delta.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// If game time is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// If enabled, update events stop being sent while the window doesn't have focus, and resume
+    /// once focus returns. Window events are still sent while paused, so the window can be focused
+    /// or closed. The time spent paused isn't included in the delta of the first update after
+    /// resuming. This is disabled by default.
+    pub fn pause_on_unfocus(&mut self, enabled: bool) {
+        self.pause_on_unfocus = enabled;
+        self.update_pause_control_flow();
+    }
+
+    /// If enabled, panics raised while the event handler is running are caught and turned into a
+    /// clean shutdown of the engine. The event handler is not called again after a panic, and the
+    /// window and graphics context are destroyed as the engine stops. The panic is still reported
+    /// through the standard panic hook, and `Engine::on_panic` can be used to respond to it. This
+    /// has no effect when compiled with `panic = "abort"`. This is disabled by default.
+    pub fn catch_panics(&mut self, enabled: bool) {
+        self.catch_panics = enabled;
+    }
+
+    /// Sets a function that's called once with the panic's message when a panic is caught, before
+    /// the engine stops. This is useful for saving work or reporting the error. It's only called
+    /// while `catch_panics` is enabled. Set this to None to remove the handler, which is the
+    /// default.
+    pub fn on_panic(&mut self, handler: Option<PanicHandler>) {
+        self.panic_handler = handler;
+    }
+}
+
+/// Gets the message a panic was raised with. Panics raised with something other than a string
+/// report a generic message.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+/// Sends a frame's messages to the output in order. The queued input is sent either before or after
+/// the fixed updates, and the update is always sent last.
+fn dispatch_frame<F: FnMut(InputMessage)>(
+    order: InputOrder,
+    queue: &mut Vec<InputMessage>,
+    fixed_steps: u32,
+    fixed_step: f32,
+    delta: f32,
+    mut output: F,
+) {
+    if order == InputOrder::BeforeFixedUpdate {
+        queue.drain(..).for_each(&mut output);
+    }
+    for _ in 0..fixed_steps {
+        output(InputMessage::FixedUpdate(fixed_step));
+    }
+    if order == InputOrder::AfterFixedUpdate {
+        queue.drain(..).for_each(&mut output);
+    }
+    output(InputMessage::Update(delta));
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_order() {
+        let input = vec![InputMessage::CloseRequested, InputMessage::WindowFocused(true)];
+        let frame = |order| {
+            let mut queue = input.clone();
+            let mut messages = Vec::new();
+            dispatch_frame(order, &mut queue, 2, 0.5, 1.0, |message| messages.push(message));
+            assert!(queue.is_empty());
+            messages
+        };
+        assert_eq!(
+            frame(InputOrder::BeforeFixedUpdate),
+            vec![
+                InputMessage::CloseRequested,
+                InputMessage::WindowFocused(true),
+                InputMessage::FixedUpdate(0.5),
+                InputMessage::FixedUpdate(0.5),
+                InputMessage::Update(1.0),
+            ]
+        );
+        assert_eq!(
+            frame(InputOrder::AfterFixedUpdate),
+            vec![
+                InputMessage::FixedUpdate(0.5),
+                InputMessage::FixedUpdate(0.5),
+                InputMessage::CloseRequested,
+                InputMessage::WindowFocused(true),
+                InputMessage::Update(1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn panics() {
+        let message = |f: fn()| panic_message(&*catch_unwind(f).unwrap_err()).to_string();
+        assert_eq!(message(|| panic!("static")), "static");
+        assert_eq!(message(|| panic!("formatted {}", 1)), "formatted 1");
+        assert_eq!(message(|| std::panic::panic_any(1)), "Box<dyn Any>");
+    }
 }
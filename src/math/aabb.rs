@@ -20,6 +20,35 @@ impl AABB2D {
         }
     }
 
+    /// Creates the smallest box containing every point. Panics if there are no points.
+    pub fn from_points(points: &[Vector2<f32>]) -> AABB2D {
+        assert!(!points.is_empty(), "An AABB requires at least one point.");
+        let mut min = points[0];
+        let mut max = points[0];
+        for point in &points[1..] {
+            min.x = min.x.min(point.x);
+            min.y = min.y.min(point.y);
+            max.x = max.x.max(point.x);
+            max.y = max.y.max(point.y);
+        }
+        AABB2D {
+            min,
+            max,
+        }
+    }
+
+    /// Creates the smallest box containing both boxes.
+    #[inline(always)]
+    pub fn merge(&self, other: &AABB2D) -> AABB2D {
+        AABB2D::new(
+            self.min.x.min(other.min.x),
+            self.min.y.min(other.min.y),
+            self.max.x.max(other.max.x),
+            self.max.y.max(other.max.y),
+        )
+    }
+
+    /// If the boxes overlap. Boxes that only share an edge or corner count as overlapping.
     #[inline(always)]
     pub fn intersects(&self, other: &AABB2D) -> bool {
         self.min.x <= other.max.x
@@ -121,6 +150,32 @@ mod tests {
     #![allow(unused_imports)]
     use super::*;
 
+    #[test]
+    fn intersects() {
+        let aabb = AABB2D::new(0.0, 0.0, 2.0, 2.0);
+        assert!(aabb.intersects(&AABB2D::new(2.0, 0.0, 3.0, 1.0)));
+        assert!(aabb.intersects(&AABB2D::new(2.0, 2.0, 3.0, 3.0)));
+        assert!(!aabb.intersects(&AABB2D::new(2.1, 0.0, 3.0, 1.0)));
+
+        let inner = AABB2D::new(0.5, 0.5, 1.5, 1.5);
+        assert!(aabb.intersects(&inner));
+        assert!(inner.intersects(&aabb));
+        assert!(aabb.contains(&inner));
+        assert!(!inner.contains(&aabb));
+        assert!(aabb.contains_point(&Vector2::new(2.0, 0.0)));
+    }
+
+    #[test]
+    fn merge() {
+        let a = AABB2D::new(0.0, 0.0, 1.0, 1.0);
+        let b = AABB2D::new(2.0, -1.0, 3.0, 0.5);
+        assert_eq!(a.merge(&b), AABB2D::new(0.0, -1.0, 3.0, 1.0));
+        assert_eq!(
+            AABB2D::from_points(&[Vector2::new(1.0, 4.0), Vector2::new(-2.0, 0.0), Vector2::new(3.0, 1.0)]),
+            AABB2D::new(-2.0, 0.0, 3.0, 4.0)
+        );
+    }
+
     #[test]
     fn slide() {
         let v = vec![AABB2D::new(2f32, 0f32, 3f32, 1f32), AABB2D::new(0f32, 1f32, 1f32, 2f32)];
@@ -0,0 +1,40 @@
+use cgmath::*;
+use core::ops::{Add, Mul, Sub};
+
+/// Linearly interpolates from `a` to `b`. An alpha of 0 gives `a` and an alpha of 1 gives `b`. This
+/// works on scalars and vectors, and is useful with `Engine::fixed_alpha` to blend between the
+/// previous and current fixed update states.
+#[inline]
+pub fn lerp<V>(a: V, b: V, alpha: f32) -> V
+where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<f32, Output = V>,
+{
+    a + (b - a) * alpha
+}
+
+/// Spherically interpolates from rotation `a` to rotation `b`, taking the shortest path at a
+/// constant angular speed. An alpha of 0 gives `a` and an alpha of 1 gives `b`.
+#[inline]
+pub fn slerp(a: Quaternion<f32>, b: Quaternion<f32>, alpha: f32) -> Quaternion<f32> {
+    a.slerp(b, alpha)
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate() {
+        assert_eq!(lerp(2.0, 4.0, 0.25), 2.5);
+        assert_eq!(lerp(Vector2::new(0.0, 10.0), Vector2::new(10.0, 0.0), 0.5), Vector2::new(5.0, 5.0));
+
+        let a = Quaternion::from_angle_z(Rad(0.0));
+        let b = Quaternion::from_angle_z(Rad(1.0));
+        let half = slerp(a, b, 0.5);
+        assert!((half - Quaternion::from_angle_z(Rad(0.5))).magnitude() < 1e-5);
+    }
+}
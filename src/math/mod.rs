@@ -1,5 +1,13 @@
 mod aabb;
+mod interpolate;
+mod projection;
+mod screen;
 mod trigonometry;
+mod vector;
 
 pub use self::aabb::*;
+pub use self::interpolate::*;
+pub use self::projection::*;
+pub use self::screen::*;
 pub use self::trigonometry::*;
+pub use self::vector::*;
@@ -0,0 +1,46 @@
+use cgmath::*;
+
+/// Creates an orthographic projection matrix. This is named apart from `cgmath::ortho` so both can
+/// be glob imported. This uses the same clip space as the renderer, which is OpenGL's, where
+/// normalized device coordinates range from -1 to 1 on every axis including Z. Near and far are
+/// distances along the view direction, which looks down -Z.
+#[inline]
+pub fn ortho_2d(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Matrix4<f32> {
+    cgmath::ortho(left, right, bottom, top, near, far)
+}
+
+/// Creates a perspective projection matrix from a vertical field of view in radians and an aspect
+/// ratio of width over height. This uses the same clip space as the renderer, which is OpenGL's,
+/// where normalized device coordinates range from -1 to 1 on every axis including Z. Near and far
+/// are positive distances along the view direction, which looks down -Z.
+#[inline]
+pub fn perspective_fov(fov_y: f32, aspect: f32, near: f32, far: f32) -> Matrix4<f32> {
+    cgmath::perspective(Rad(fov_y), aspect, near, far)
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(matrix: Matrix4<f32>, point: Vector3<f32>) -> Vector3<f32> {
+        let clip = matrix * point.extend(1.0);
+        clip.truncate() / clip.w
+    }
+
+    #[test]
+    fn projection() {
+        let ortho = ortho_2d(-100.0, 100.0, -50.0, 50.0, -1.0, 1.0);
+        assert_eq!(project(ortho, Vector3::new(50.0, -50.0, 0.0)), Vector3::new(0.5, -1.0, 0.0));
+        assert_eq!(project(ortho, Vector3::new(0.0, 0.0, 1.0)), Vector3::new(0.0, 0.0, -1.0));
+
+        let perspective = perspective_fov(core::f32::consts::FRAC_PI_2, 2.0, 1.0, 10.0);
+        let near = project(perspective, Vector3::new(2.0, 1.0, -1.0));
+        assert!((near - Vector3::new(1.0, 1.0, -1.0)).magnitude() < 1e-5);
+        let far = project(perspective, Vector3::new(0.0, 0.0, -10.0));
+        assert!((far.z - 1.0).abs() < 1e-5);
+    }
+}
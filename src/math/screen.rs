@@ -0,0 +1,16 @@
+use cgmath::*;
+
+/// Converts a position from the engine's screen coordinates into normalized device coordinates.
+/// Screen coordinates are measured in pixels from the center of the window with Y pointing up, and
+/// normalized device coordinates range from -1 to 1 across the window.
+#[inline]
+pub fn to_ndc(pixels: Vector2<f32>, window_size: Vector2<f32>) -> Vector2<f32> {
+    Vector2::new(pixels.x * 2.0 / window_size.x, pixels.y * 2.0 / window_size.y)
+}
+
+/// Converts a position from normalized device coordinates into the engine's screen coordinates.
+/// This is the inverse of `to_ndc`.
+#[inline]
+pub fn to_pixels(ndc: Vector2<f32>, window_size: Vector2<f32>) -> Vector2<f32> {
+    Vector2::new(ndc.x * window_size.x / 2.0, ndc.y * window_size.y / 2.0)
+}
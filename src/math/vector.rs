@@ -0,0 +1,64 @@
+use cgmath::*;
+
+/// A 2D vector of f32.
+pub type Vec2 = Vector2<f32>;
+/// A 3D vector of f32.
+pub type Vec3 = Vector3<f32>;
+/// A 4D vector of f32.
+pub type Vec4 = Vector4<f32>;
+/// A 4x4 matrix of f32.
+pub type Mat4 = Matrix4<f32>;
+
+/// Rotates the vector counterclockwise by the given angle in radians.
+#[inline]
+pub fn rotate_vec2(v: Vec2, radians: f32) -> Vec2 {
+    let (sin, cos) = radians.sin_cos();
+    Vector2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// Gets the signed angle in radians to rotate `a` counterclockwise to point in the direction of
+/// `b`. The result is in the range [-PI, PI].
+#[inline]
+pub fn angle_between(a: Vec2, b: Vec2) -> f32 {
+    a.perp_dot(b).atan2(a.dot(b))
+}
+
+/// Reflects the vector off of a surface with the given normal. The normal is expected to be
+/// normalized.
+#[inline]
+pub fn reflect(v: Vec2, normal: Vec2) -> Vec2 {
+    v - normal * (2.0 * v.dot(normal))
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).magnitude() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn rotate() {
+        assert_near(rotate_vec2(Vec2::new(1.0, 0.0), FRAC_PI_2), Vec2::new(0.0, 1.0));
+        assert_near(rotate_vec2(Vec2::new(1.0, 2.0), PI), Vec2::new(-1.0, -2.0));
+    }
+
+    #[test]
+    fn angle() {
+        assert!((angle_between(Vec2::new(1.0, 0.0), Vec2::new(0.0, 2.0)) - FRAC_PI_2).abs() < 1e-5);
+        assert!((angle_between(Vec2::new(1.0, 0.0), Vec2::new(0.0, -1.0)) + FRAC_PI_2).abs() < 1e-5);
+        assert_eq!(angle_between(Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn reflection() {
+        assert_near(reflect(Vec2::new(1.0, -1.0), Vec2::new(0.0, 1.0)), Vec2::new(1.0, 1.0));
+        assert_near(reflect(Vec2::new(-2.0, 3.0), Vec2::new(1.0, 0.0)), Vec2::new(2.0, 3.0));
+    }
+}
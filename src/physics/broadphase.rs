@@ -0,0 +1,76 @@
+use crate::math::AABB2D;
+use core::cmp::Ordering;
+
+/// Finds the pairs of boxes that may be colliding, so narrow collision checks only run on boxes
+/// that are near each other. This uses sort and sweep along the X axis. The buffers used are kept
+/// between calls, so finding pairs every frame doesn't reallocate once they've grown.
+#[derive(Default)]
+pub struct BroadPhase {
+    sorted: Vec<(u32, AABB2D)>,
+    pairs: Vec<(u32, u32)>,
+}
+
+impl BroadPhase {
+    /// Creates a new empty broad phase.
+    pub fn new() -> BroadPhase {
+        BroadPhase::default()
+    }
+
+    /// Gets every pair of boxes that overlap, identified by the IDs they were given with. Boxes
+    /// that only share an edge count as overlapping. The pairs, and the order of the IDs within a
+    /// pair, are in no particular order.
+    pub fn pairs(&mut self, boxes: &[(u32, AABB2D)]) -> &[(u32, u32)] {
+        self.sorted.clear();
+        self.sorted.extend_from_slice(boxes);
+        self.sorted.sort_unstable_by(|a, b| a.1.min.x.partial_cmp(&b.1.min.x).unwrap_or(Ordering::Equal));
+
+        self.pairs.clear();
+        for (index, &(id, aabb)) in self.sorted.iter().enumerate() {
+            for &(other_id, other) in &self.sorted[index + 1..] {
+                // Boxes are sorted by their left edge, so once one starts past this box's right
+                // edge, every box after it does too.
+                if other.min.x > aabb.max.x {
+                    break;
+                }
+                if aabb.intersects(&other) {
+                    self.pairs.push((id, other_id));
+                }
+            }
+        }
+        &self.pairs
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(pairs: &[(u32, u32)]) -> Vec<(u32, u32)> {
+        let mut pairs: Vec<(u32, u32)> = pairs.iter().map(|&(a, b)| (a.min(b), a.max(b))).collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    #[test]
+    fn pairs() {
+        let mut broad = BroadPhase::new();
+        let boxes = [
+            // A cluster of overlapping boxes.
+            (0, AABB2D::new(0.0, 0.0, 2.0, 2.0)),
+            (1, AABB2D::new(1.0, 1.0, 3.0, 3.0)),
+            (2, AABB2D::new(2.0, 0.0, 4.0, 1.0)),
+            // Overlaps on X with the cluster, but not on Y.
+            (3, AABB2D::new(0.0, 10.0, 4.0, 11.0)),
+            // A disjoint cluster.
+            (4, AABB2D::new(20.0, 0.0, 21.0, 1.0)),
+            (5, AABB2D::new(20.5, 0.5, 22.0, 2.0)),
+            (6, AABB2D::new(30.0, 0.0, 31.0, 1.0)),
+        ];
+        assert_eq!(sorted(broad.pairs(&boxes)), vec![(0, 1), (0, 2), (1, 2), (4, 5)]);
+        assert_eq!(broad.pairs(&boxes[5..]), &[]);
+    }
+}
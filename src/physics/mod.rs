@@ -0,0 +1,5 @@
+mod broadphase;
+mod raycast;
+
+pub use self::broadphase::*;
+pub use self::raycast::*;
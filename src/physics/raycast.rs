@@ -0,0 +1,96 @@
+use crate::math::AABB2D;
+use cgmath::*;
+
+/// The result of a raycast.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RayHit {
+    /// The ID of the box that was hit.
+    pub entity: u32,
+    /// How far along the direction the hit is. The hit point is `origin + dir * t`, so this is the
+    /// distance when the direction is normalized.
+    pub t: f32,
+    /// The point the ray entered the box. If the origin is inside the box, this is the origin.
+    pub point: Vector2<f32>,
+}
+
+/// Casts a ray from the origin in the given direction, returning the nearest box it hits. Boxes
+/// behind the origin aren't hit, and a ray starting inside a box hits it at the origin.
+pub fn raycast(origin: Vector2<f32>, dir: Vector2<f32>, boxes: &[(u32, AABB2D)]) -> Option<RayHit> {
+    let mut nearest: Option<RayHit> = None;
+    for &(entity, aabb) in boxes {
+        if let Some(t) = ray_aabb(origin, dir, &aabb) {
+            let closer = match nearest {
+                Some(hit) => t < hit.t,
+                None => true,
+            };
+            if closer {
+                nearest = Some(RayHit {
+                    entity,
+                    t,
+                    point: origin + dir * t,
+                });
+            }
+        }
+    }
+    nearest
+}
+
+/// Finds where the ray enters the box using the slab method, returning how far along the direction
+/// it is.
+fn ray_aabb(origin: Vector2<f32>, dir: Vector2<f32>, aabb: &AABB2D) -> Option<f32> {
+    let mut t_min = 0.0f32;
+    let mut t_max = f32::INFINITY;
+    for axis in 0..2 {
+        let (origin, dir, min, max) = (origin[axis], dir[axis], aabb.min[axis], aabb.max[axis]);
+        if dir == 0.0 {
+            // Parallel to the slab, so it never enters or leaves it. Dividing here would produce
+            // NaN when the origin is on the slab's edge.
+            if origin < min || origin > max {
+                return None;
+            }
+        } else {
+            let near = (min - origin) / dir;
+            let far = (max - origin) / dir;
+            t_min = t_min.max(near.min(far));
+            t_max = t_max.min(near.max(far));
+            if t_min > t_max {
+                return None;
+            }
+        }
+    }
+    Some(t_min)
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raycast() {
+        let boxes = [(1, AABB2D::new(4.0, -1.0, 5.0, 1.0)), (2, AABB2D::new(2.0, -1.0, 3.0, 1.0))];
+        let right = Vector2::new(1.0, 0.0);
+        assert_eq!(
+            super::raycast(Vector2::new(0.0, 0.0), right, &boxes),
+            Some(RayHit {
+                entity: 2,
+                t: 2.0,
+                point: Vector2::new(2.0, 0.0),
+            })
+        );
+        assert_eq!(super::raycast(Vector2::new(0.0, 0.0), -right, &boxes), None);
+
+        // Axis parallel rays on a box's edge still hit, and rays beside it miss.
+        assert_eq!(super::raycast(Vector2::new(0.0, 1.0), right, &boxes).map(|hit| hit.entity), Some(2));
+        assert_eq!(super::raycast(Vector2::new(0.0, 1.5), right, &boxes), None);
+
+        // Diagonal rays and rays starting inside a box.
+        let hit = super::raycast(Vector2::new(0.0, -3.0), Vector2::new(1.0, 1.0), &boxes).unwrap();
+        assert_eq!((hit.entity, hit.t), (2, 2.0));
+        let hit = super::raycast(Vector2::new(4.5, 0.0), right, &boxes).unwrap();
+        assert_eq!((hit.entity, hit.t), (1, 0.0));
+    }
+}
@@ -9,6 +9,7 @@ pub struct Buffer<T: VertexDescription + Copy> {
     vbo: resource::Buffer,
     vao: resource::VertexArray,
     vertices: usize,
+    capacity: usize,
     buffer_type: BufferBindingTarget,
     phantom: PhantomData<T>,
 }
@@ -17,7 +18,7 @@ impl<T: VertexDescription + Copy> Buffer<T> {
     pub fn new(state: UnsafeShared<OpenGLState>, buffer_type: BufferBindingTarget) -> Buffer<T> {
         let vbo = state.gl.create_buffer();
         state.gl.bind_buffer(buffer_type, Some(vbo));
-        state.gl.buffer_data_empty(buffer_type, 0, BufferUsage::StaticDraw);
+        state.gl.buffer_data_empty(buffer_type, 0, BufferUsage::StreamDraw);
 
         let vao = state.gl.create_vertex_array();
         state.gl.bind_vertex_array(Some(vao));
@@ -28,6 +29,7 @@ impl<T: VertexDescription + Copy> Buffer<T> {
             vbo,
             vao,
             vertices: 0,
+            capacity: 0,
             buffer_type,
             phantom: PhantomData,
         }
@@ -37,15 +39,58 @@ impl<T: VertexDescription + Copy> Buffer<T> {
         self.vertices
     }
 
+    /// Removes every item without shrinking the storage.
     pub fn clear(&mut self) {
         self.vertices = 0;
     }
 
+    /// Grows the storage to hold at least the given number of items, so later sets up to that size
+    /// don't need to grow it. The current items are kept. Storage is never shrunk.
+    pub fn reserve(&mut self, capacity: usize) {
+        if capacity <= self.capacity {
+            return;
+        }
+        self.capacity = capacity;
+        let size = (self.capacity * T::VERTEX_SIZE) as i32;
+        if self.vertices == 0 {
+            self.state.gl.bind_buffer(self.buffer_type, Some(self.vbo));
+            self.state.gl.buffer_data_empty(self.buffer_type, size, BufferUsage::StreamDraw);
+            return;
+        }
+
+        // Growing the storage discards it, so the current items are copied into a new buffer, and
+        // the vertex array is pointed at it.
+        let gl = &self.state.gl;
+        let vbo = gl.create_buffer();
+        gl.bind_buffer(BufferBindingTarget::CopyWriteBuffer, Some(vbo));
+        gl.buffer_data_empty(BufferBindingTarget::CopyWriteBuffer, size, BufferUsage::StreamDraw);
+        gl.bind_buffer(BufferBindingTarget::CopyReadBuffer, Some(self.vbo));
+        gl.copy_buffer_sub_data(
+            BufferBindingTarget::CopyReadBuffer,
+            BufferBindingTarget::CopyWriteBuffer,
+            0,
+            0,
+            (self.vertices * T::VERTEX_SIZE) as i32,
+        );
+        gl.delete_buffer(self.vbo);
+        self.vbo = vbo;
+        gl.bind_vertex_array(Some(self.vao));
+        gl.bind_buffer(self.buffer_type, Some(self.vbo));
+        T::configure_vertex_attribute(gl);
+    }
+
     pub fn set(&mut self, items: &Vec<T>) {
         self.vertices = items.len();
         if self.vertices > 0 {
+            if self.vertices > self.capacity {
+                self.capacity = self.vertices.next_power_of_two();
+            }
+            // Orphan the previous storage before writing into it. The driver hands back fresh
+            // memory instead of stalling until draws still reading the old contents have finished.
+            let size = (self.capacity * T::VERTEX_SIZE) as i32;
             self.state.gl.bind_buffer(self.buffer_type, Some(self.vbo));
-            self.state.gl.buffer_data(self.buffer_type, items, BufferUsage::StaticDraw);
+            self.state.gl.buffer_data_empty(self.buffer_type, size, BufferUsage::StreamDraw);
+            self.state.gl.buffer_sub_data(self.buffer_type, items);
         }
     }
 
@@ -53,6 +98,7 @@ impl<T: VertexDescription + Copy> Buffer<T> {
         if self.vertices > 0 {
             self.state.gl.bind_vertex_array(Some(self.vao));
             self.state.gl.draw_arrays_instanced(DrawMode::TriangleStrip, 0, 4, self.vertices as i32);
+            self.state.count_draw_call();
         }
     }
 }
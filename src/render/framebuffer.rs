@@ -0,0 +1,145 @@
+use crate::render::raw::{
+    resource, FramebufferAttachment, PixelFormat, PixelInternalFormat, PixelType, TextureBindingTarget,
+    TextureLoadTarget, TextureMagFilterValue, TextureMinFilterValue, TextureParameterTarget, TextureUnit,
+    TextureWrapValue,
+};
+use crate::render::OpenGLState;
+use crate::types::{FramebufferError, Texture};
+use crate::utility::bad::UnsafeShared;
+use cgmath::*;
+
+/// An offscreen target that draws into its own color texture instead of the window. Draws made
+/// while it's bound go to the texture, which layers can then sample with `Layer::draw_sampling`.
+///
+/// OpenGL stores the rows of the texture from bottom to top, the reverse of the atlas and of
+/// `Engine::window_capture`. Sprites given `Framebuffer::texture` are already flipped to draw the
+/// result upright, so the coordinate flip only matters when reading the texture another way.
+pub struct Framebuffer {
+    state: UnsafeShared<OpenGLState>,
+    id: resource::Framebuffer,
+    color: resource::Texture,
+    depth: Option<resource::Renderbuffer>,
+    size: Vector2<u32>,
+    texture: Texture,
+}
+
+impl Framebuffer {
+    pub(crate) fn new(
+        state: UnsafeShared<OpenGLState>,
+        width: u32,
+        height: u32,
+        depth: bool,
+    ) -> Result<Framebuffer, FramebufferError> {
+        let gl = &state.gl;
+        let id = gl.create_framebuffer();
+        let color = gl.create_texture();
+        gl.active_texture(TextureUnit::Framebuffer);
+        gl.bind_texture(TextureBindingTarget::Texture2D, Some(color));
+        gl.tex_parameter_wrap_s(TextureParameterTarget::Texture2D, TextureWrapValue::ClampToEdge);
+        gl.tex_parameter_wrap_t(TextureParameterTarget::Texture2D, TextureWrapValue::ClampToEdge);
+        gl.tex_parameter_min_filter(TextureParameterTarget::Texture2D, TextureMinFilterValue::Nearest);
+        gl.tex_parameter_mag_filter(TextureParameterTarget::Texture2D, TextureMagFilterValue::Nearest);
+        let depth = if depth {
+            Some(gl.create_renderbuffer())
+        } else {
+            None
+        };
+
+        gl.bind_framebuffer(Some(id));
+        gl.framebuffer_texture_2d(FramebufferAttachment::Color0, Some(color));
+        if let Some(depth) = depth {
+            gl.bind_renderbuffer(Some(depth));
+            gl.framebuffer_renderbuffer(FramebufferAttachment::DepthStencil, Some(depth));
+        }
+        gl.bind_framebuffer(None);
+
+        let mut framebuffer = Framebuffer {
+            state,
+            id,
+            color,
+            depth,
+            size: Vector2::new(0, 0),
+            // The texture is flipped vertically to undo the bottom to top row order.
+            texture: Texture(Vector4::new(0, u16::MAX, u16::MAX, 0)),
+        };
+        // Dropping the framebuffer on error deletes everything created above.
+        framebuffer.resize(width, height)?;
+        Ok(framebuffer)
+    }
+
+    /// Resizes the framebuffer, recreating the storage of its attachments. The current contents
+    /// are lost, and draws go back to the window. This is commonly called with the window's new
+    /// physical size after the window is resized, so the framebuffer keeps drawing layers the same
+    /// as the window does.
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), FramebufferError> {
+        let gl = &self.state.gl;
+        gl.active_texture(TextureUnit::Framebuffer);
+        gl.bind_texture(TextureBindingTarget::Texture2D, Some(self.color));
+        gl.tex_image_2d_empty(
+            TextureLoadTarget::Texture2D,
+            width as i32,
+            height as i32,
+            PixelInternalFormat::RGBA,
+            PixelFormat::RGBA,
+            PixelType::UnsignedByte,
+        );
+        if let Some(depth) = self.depth {
+            gl.bind_renderbuffer(Some(depth));
+            gl.renderbuffer_storage_depth_stencil(width as i32, height as i32);
+        }
+        self.size = Vector2::new(width, height);
+
+        gl.bind_framebuffer(Some(self.id));
+        let status = gl.check_framebuffer_status();
+        self.state.framebuffer_unbind();
+        if status != glow::FRAMEBUFFER_COMPLETE {
+            return Err(FramebufferError::new(status));
+        }
+        Ok(())
+    }
+
+    /// Directs following draws into this framebuffer's texture, with the viewport covering the
+    /// whole texture. Layers keep their window projection, so a framebuffer the size of the
+    /// window's physical size draws layers the same as the window does.
+    pub fn bind(&self) {
+        self.state.gl.bind_framebuffer(Some(self.id));
+        self.state.gl.viewport(0, 0, self.size.x as i32, self.size.y as i32);
+    }
+
+    /// Directs following draws back into the window, restoring its viewport.
+    pub fn unbind(&self) {
+        self.state.framebuffer_unbind();
+    }
+
+    /// Gets the texture to give sprites to draw the whole color texture of this framebuffer. Draw
+    /// them with `Layer::draw_sampling`.
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Gets the size of the attachments in pixels.
+    pub fn size(&self) -> Vector2<u32> {
+        self.size
+    }
+
+    /// Gets if the framebuffer has a depth and stencil attachment.
+    pub fn has_depth(&self) -> bool {
+        self.depth.is_some()
+    }
+
+    /// Binds the color texture to the framebuffer texture unit for sampling.
+    pub(crate) fn bind_texture(&self) {
+        self.state.gl.active_texture(TextureUnit::Framebuffer);
+        self.state.gl.bind_texture(TextureBindingTarget::Texture2D, Some(self.color));
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        self.state.gl.delete_framebuffer(self.id);
+        self.state.gl.delete_texture(self.color);
+        if let Some(depth) = self.depth {
+            self.state.gl.delete_renderbuffer(depth);
+        }
+    }
+}
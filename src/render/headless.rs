@@ -0,0 +1,74 @@
+use crate::render::raw::OpenGL;
+use crate::render::OpenGLState;
+use crate::utility::bad::UnsafeShared;
+
+/// Creates renderer state on a surfaceless OpenGL context, made current on the calling thread, so
+/// tests can draw and read back pixels without a window. Returns None if the platform can't create
+/// one, such as when Mesa's EGL isn't installed, and the calling test should then return early.
+pub fn state() -> Option<UnsafeShared<OpenGLState>> {
+    match context() {
+        Some(gl) => Some(UnsafeShared::new(OpenGLState::new(OpenGL::new(gl)))),
+        None => {
+            warn!("Skipping test, no surfaceless OpenGL context is available.");
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn context() -> Option<glow::Context> {
+    use core::ptr::null;
+    use glutin_egl_sys::egl;
+    use std::ffi::CString;
+
+    const PLATFORM_SURFACELESS_MESA: egl::types::EGLenum = 0x31DD;
+
+    unsafe {
+        let library = libloading::Library::new("libEGL.so.1").ok()?;
+        let egl = egl::Egl::load_with(|name| {
+            let name = CString::new(name).unwrap();
+            match library.get::<*const core::ffi::c_void>(name.as_bytes_with_nul()) {
+                Ok(symbol) => *symbol,
+                Err(_) => null(),
+            }
+        });
+        // The context outlives the test, so the library is never unloaded.
+        core::mem::forget(library);
+
+        let display =
+            egl.GetPlatformDisplay(PLATFORM_SURFACELESS_MESA, egl::DEFAULT_DISPLAY as *mut _, null());
+        if display == egl::NO_DISPLAY {
+            return None;
+        }
+        let (mut major, mut minor) = (0, 0);
+        if egl.Initialize(display, &mut major, &mut minor) == egl::FALSE
+            || egl.BindAPI(egl::OPENGL_API) == egl::FALSE
+        {
+            return None;
+        }
+        let attributes = [
+            egl::CONTEXT_MAJOR_VERSION as i32,
+            3,
+            egl::CONTEXT_MINOR_VERSION as i32,
+            3,
+            egl::CONTEXT_OPENGL_PROFILE_MASK as i32,
+            egl::CONTEXT_OPENGL_CORE_PROFILE_BIT as i32,
+            egl::NONE as i32,
+        ];
+        let context = egl.CreateContext(display, null(), egl::NO_CONTEXT, attributes.as_ptr());
+        if context == egl::NO_CONTEXT
+            || egl.MakeCurrent(display, egl::NO_SURFACE, egl::NO_SURFACE, context) == egl::FALSE
+        {
+            return None;
+        }
+        Some(glow::Context::from_loader_function(|name| {
+            let name = CString::new(name).unwrap();
+            egl.GetProcAddress(name.as_ptr()) as *const _
+        }))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn context() -> Option<glow::Context> {
+    None
+}
@@ -1,7 +1,8 @@
 use crate::render::buffer::Buffer;
-use crate::render::raw::BufferBindingTarget;
+use crate::render::framebuffer::Framebuffer;
+use crate::render::raw::{BufferBindingTarget, TextureUnit};
 use crate::render::OpenGLState;
-use crate::types::{LayerTransform, Sprite};
+use crate::types::{BlendMode, LayerTransform, Sprite, RGBA8};
 use crate::utility::bad::UnsafeShared;
 use cgmath::*;
 
@@ -41,6 +42,9 @@ pub struct Layer {
     state: UnsafeShared<OpenGLState>,
     shared: UnsafeShared<SharedLayer>,
     is_visible: bool,
+    outline_color: RGBA8,
+    outline_thickness: u8,
+    blend_mode: BlendMode,
     sprites: Buffer<Sprite>,
 }
 
@@ -59,6 +63,9 @@ impl Layer {
             state: state.clone(),
             shared: shared.clone(),
             is_visible: true,
+            outline_color: RGBA8::new_raw(0, 0, 0, 0),
+            outline_thickness: 0,
+            blend_mode: BlendMode::Alpha,
             sprites: Buffer::new(state, BufferBindingTarget::ArrayBuffer),
         };
         (shared, layer)
@@ -67,16 +74,36 @@ impl Layer {
     pub fn draw(&mut self) {
         if self.is_visible && self.sprites.len() > 0 {
             self.state.shader_ortho(&self.shared.ortho_transform);
+            self.state.shader_outline(self.outline_color, self.outline_thickness);
+            self.state.blend_mode(self.blend_mode);
             self.sprites.draw();
         }
     }
 
+    /// Draws this layer with its sprites sampling the framebuffer's color texture instead of the
+    /// texture atlas. Give the sprites `Framebuffer::texture` to draw the whole texture. The
+    /// framebuffer can't be bound while it's sampled.
+    pub fn draw_sampling(&mut self, framebuffer: &Framebuffer) {
+        framebuffer.bind_texture();
+        self.state.shader_texture(TextureUnit::Framebuffer);
+        self.draw();
+        self.state.shader_texture(TextureUnit::Atlas);
+    }
+
     /// Sets the sprites that will be drawn.
     pub fn set_sprites(&mut self, sprites: &Vec<Sprite>) {
         self.sprites.set(sprites);
     }
 
-    /// Clears all the sprites, drawing nothing.
+    /// Reserves room for at least the given number of sprites, so setting that many sprites later
+    /// doesn't need to grow the layer's storage. The current sprites are kept. Storage is never
+    /// shrunk, including when sprites are cleared.
+    pub fn reserve_sprites(&mut self, capacity: usize) {
+        self.sprites.reserve(capacity);
+    }
+
+    /// Clears all the sprites, drawing nothing. The storage isn't shrunk, so setting as many sprites
+    /// again doesn't need to grow it.
     pub fn clear_sprites(&mut self) {
         self.sprites.clear();
     }
@@ -91,6 +118,23 @@ impl Layer {
         self.shared.set_transform_matrix(transform);
     }
 
+    /// Draws an outline around the opaque parts of every sprite in this layer. The outline is drawn
+    /// over transparent texels within the thickness of an opaque texel, so sprites need transparent
+    /// padding for the outline to be visible along their edges. Thickness is measured in texels and
+    /// is limited to 8. A thickness of 0 disables the outline, which is the default.
+    pub fn set_outline(&mut self, color: RGBA8, thickness: u8) {
+        self.outline_color = color;
+        self.outline_thickness = thickness.min(8);
+    }
+
+    /// Sets how this layer's sprites are blended with what's drawn behind them. Each sprite's color
+    /// already tints it and its alpha fades it. Sprites still write depth, so within a layer a
+    /// translucent sprite hides sprites drawn after it that are further back. The default is
+    /// `BlendMode::Alpha`.
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.blend_mode = mode;
+    }
+
     /// If the renderer should render this layer or not when draw is called.
     pub fn set_visible(&mut self, is_visible: bool) {
         self.is_visible = is_visible;
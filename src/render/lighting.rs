@@ -0,0 +1,239 @@
+use crate::render::buffer::Buffer;
+use crate::render::framebuffer::Framebuffer;
+use crate::render::layer::Layer;
+use crate::render::raw::{BufferBindingTarget, Capability, TextureUnit};
+use crate::render::shader::{light, texture, Shader};
+use crate::render::OpenGLState;
+use crate::types::*;
+use crate::utility::bad::UnsafeShared;
+use cgmath::*;
+
+/// A light that shines equally in every direction from a point.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointLight {
+    /// The center of the light, in the same coordinates as sprites.
+    pub pos: Vector2<f32>,
+    /// The distance from the center at which the light fades out completely.
+    pub radius: f32,
+    /// The color of the light. Its alpha is ignored.
+    pub color: RGBA8,
+    /// How bright the light is at its center, from 0 to 1.
+    pub intensity: f32,
+}
+
+/// A 2D lighting pass. Point lights are added together into a light map, which is then multiplied
+/// over the scene, so areas without any light show only the ambient color. The light map is an
+/// offscreen framebuffer, so it can be smaller than the window for cheaper, softer lights.
+///
+/// Lights are positioned like the sprites of a layer, and `set_transform` should match the
+/// transform of the layers being lit so the lights line up with them.
+pub struct Lighting {
+    state: UnsafeShared<OpenGLState>,
+    map: Framebuffer,
+    shader: Shader,
+    layer: Layer,
+    ambient: Buffer<Sprite>,
+    composite: Buffer<Sprite>,
+    lights: Vec<PointLight>,
+    sprites: Vec<Sprite>,
+}
+
+impl Lighting {
+    pub(crate) fn new(
+        mut state: UnsafeShared<OpenGLState>,
+        ortho: &Matrix4<f32>,
+        width: u32,
+        height: u32,
+    ) -> Result<Lighting, FramebufferError> {
+        let map = Framebuffer::new(state.clone(), width, height, false)?;
+        let shader = Shader::from_source(state.clone(), texture::VERTEX, light::FRAGMENT).unwrap();
+        let (shared, mut layer) = Layer::new(state.clone(), ortho);
+        state.layer_add(shared);
+        layer.set_blend_mode(BlendMode::Additive);
+        // The ambient fill and the composite cover the whole target in clip space.
+        let fill = Sprite {
+            pos: Vector3::new(-1.0, -1.0, 0.0),
+            size: Vector2::new(2, 2),
+            ..Sprite::default()
+        };
+        let ambient = Buffer::new(state.clone(), BufferBindingTarget::ArrayBuffer);
+        let mut composite = Buffer::new(state.clone(), BufferBindingTarget::ArrayBuffer);
+        composite.set(&vec![Sprite {
+            texture: *map.texture(),
+            ..fill
+        }]);
+        let mut lighting = Lighting {
+            state,
+            map,
+            shader,
+            layer,
+            ambient,
+            composite,
+            lights: Vec::new(),
+            sprites: Vec::new(),
+        };
+        lighting.set_ambient(colors::BLACK);
+        lighting.set_falloff(2.0);
+        Ok(lighting)
+    }
+
+    /// Sets the light everything receives, even without a light nearby. The default is black,
+    /// so only lit areas are visible.
+    pub fn set_ambient(&mut self, color: RGBA8) {
+        self.ambient.set(&vec![Sprite {
+            pos: Vector3::new(-1.0, -1.0, 0.0),
+            size: Vector2::new(2, 2),
+            color: RGBA8 {
+                a: 255,
+                ..color
+            },
+            ..Sprite::default()
+        }]);
+    }
+
+    /// Sets how quickly lights fade from their center to their radius. Light falls off with the
+    /// distance from the center raised to this power, so 1 fades linearly and larger values
+    /// concentrate the light around the center. The default is 2.
+    pub fn set_falloff(&mut self, falloff: f32) {
+        self.shader.uniform_f32("falloff", falloff.max(0.0));
+    }
+
+    /// Adds a light. Lights are kept until they're cleared.
+    pub fn add_light(&mut self, light: PointLight) {
+        self.lights.push(light);
+    }
+
+    /// Gets the lights added so far.
+    pub fn lights(&self) -> &[PointLight] {
+        &self.lights
+    }
+
+    /// Removes every light.
+    pub fn clear_lights(&mut self) {
+        self.lights.clear();
+    }
+
+    /// Sets the transformation matrix the lights are drawn with.
+    pub fn set_transform(&mut self, transform: &LayerTransform) {
+        self.layer.set_transform(transform);
+    }
+
+    /// Sets the transformation matrix the lights are drawn with.
+    pub fn set_transform_matrix(&mut self, transform: &Matrix4<f32>) {
+        self.layer.set_transform_matrix(transform);
+    }
+
+    /// Resizes the light map. The lights are drawn scaled to fit it, so this only changes the
+    /// resolution of the lighting. This is commonly called with the window's new physical size
+    /// after the window is resized.
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), FramebufferError> {
+        self.map.resize(width, height)
+    }
+
+    /// Draws the ambient color and every light into the light map. Afterwards, draws go to the
+    /// window again.
+    pub fn render(&mut self) {
+        self.sprites.clear();
+        for light in &self.lights {
+            let size = (light.radius * 2.0).round().max(0.0) as u16;
+            let radius = size as f32 / 2.0;
+            self.sprites.push(Sprite {
+                pos: Vector3::new(light.pos.x - radius, light.pos.y - radius, 0.0),
+                size: Vector2::new(size, size),
+                // The light shader measures the distance from the center through the uv, so it
+                // spans the whole range.
+                texture: Texture(Vector4::new(0, u16::MAX, 0, u16::MAX)),
+                color: RGBA8 {
+                    a: (light.intensity.clamp(0.0, 1.0) * 255.0).round() as u8,
+                    ..light.color
+                },
+                rotation: 0,
+            });
+        }
+        self.layer.set_sprites(&self.sprites);
+
+        let (program, uniforms) = self.state.shader_active();
+        self.state.gl.disable(Capability::DepthTest);
+        self.map.bind();
+        self.state.shader_reset();
+        self.fill(BlendMode::None);
+        self.ambient.draw();
+        self.shader.begin();
+        self.layer.draw();
+        self.map.unbind();
+        self.state.gl.enable(Capability::DepthTest);
+        self.state.shader_use(program, &uniforms);
+    }
+
+    /// Multiplies whatever has been drawn so far by the light map from the last `render`. This
+    /// covers the window or the framebuffer being drawn into, ignoring depth, so it's usually
+    /// called after the scene is drawn and before anything that shouldn't be lit, such as a HUD.
+    pub fn draw(&mut self) {
+        let (program, uniforms) = self.state.shader_active();
+        self.state.gl.disable(Capability::DepthTest);
+        self.state.shader_reset();
+        self.fill(BlendMode::Multiply);
+        self.map.bind_texture();
+        self.state.shader_texture(TextureUnit::Framebuffer);
+        self.composite.draw();
+        self.state.gl.enable(Capability::DepthTest);
+        self.state.shader_use(program, &uniforms);
+    }
+
+    /// Prepares the built in shader to draw a sprite covering the target.
+    fn fill(&mut self, mode: BlendMode) {
+        self.state.shader_ortho(&Matrix4::identity());
+        self.state.shader_outline(RGBA8::new_raw(0, 0, 0, 0), 0);
+        self.state.blend_mode(mode);
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::headless;
+    use crate::render::raw::{ClearMode, PixelFormat, PixelType};
+    use crate::render::texture_handle::TextureHandle;
+
+    #[test]
+    fn lighting() {
+        let mut state = match headless::state() {
+            Some(state) => state,
+            None => return,
+        };
+        let _atlas = TextureHandle::new(state.clone(), TextureUnit::Atlas);
+        let scene = Framebuffer::new(state.clone(), 8, 8, false).unwrap();
+        // The lights are positioned in clip space, so the 8x8 map is 2 units across.
+        let mut lighting = Lighting::new(state.clone(), &Matrix4::identity(), 8, 8).unwrap();
+        lighting.set_ambient(RGBA8::new_raw(64, 64, 64, 0));
+        lighting.set_falloff(1.0);
+        lighting.add_light(PointLight {
+            pos: Vector2::new(0.0, 0.0),
+            radius: 1.0,
+            color: RGBA8::new_raw(255, 0, 0, 255),
+            intensity: 1.0,
+        });
+        let pixel = |state: &UnsafeShared<OpenGLState>, x: i32, y: i32| {
+            let mut pixel = [0u8; 4];
+            state.gl.read_pixels(x, y, 1, 1, PixelFormat::RGBA, PixelType::UnsignedByte, &mut pixel);
+            pixel
+        };
+
+        lighting.render();
+        state.gl.clear_color(1.0, 1.0, 1.0, 1.0);
+        scene.bind();
+        state.gl.clear(ClearMode::COLOR);
+        lighting.draw();
+
+        // The corners only get the ambient light, and the center adds the red light on top.
+        assert_eq!(&pixel(&state, 0, 0)[..3], &[64, 64, 64]);
+        let center = pixel(&state, 4, 4);
+        assert!(center[0] > 200 && center[1] == 64 && center[2] == 64);
+        scene.unbind();
+        state.gl.clear_color(0.0, 0.0, 0.0, 1.0);
+    }
+}
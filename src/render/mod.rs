@@ -1,24 +1,36 @@
 mod buffer;
+mod framebuffer;
+#[cfg(test)]
+mod headless;
 mod layer;
+mod lighting;
 mod raw;
 mod shader;
+mod shapes;
 mod state;
 mod texture_handle;
+mod tilemap;
 mod vertex;
 mod window;
 
-use self::raw::{OpenGL, TextureUnit};
+use self::raw::{OpenGL, PixelFormat, PixelType, StringTarget, TextureUnit};
 use self::state::OpenGLState;
 use self::texture_handle::*;
 use self::window::*;
+use crate::math::AABB2D;
 use crate::text::*;
 use crate::texture::*;
 use crate::types::*;
 use crate::utility::bad::UnsafeShared;
 use cgmath::*;
 
+pub use self::framebuffer::Framebuffer;
 pub use self::layer::Layer;
+pub use self::lighting::{Lighting, PointLight};
 pub use self::raw::ClearMode;
+pub use self::shader::Shader;
+pub use self::shapes::ShapeRenderer;
+pub use self::tilemap::Tilemap;
 
 pub fn matrix_from_bounds(bounds: &Vector2<f32>) -> Matrix4<f32> {
     let w = bounds.x / 2.0;
@@ -37,24 +49,29 @@ pub struct Renderer {
 }
 
 impl Renderer {
-    pub fn new(desc: &WindowSettings, event_loop: &winit::event_loop::EventLoop<()>) -> Renderer {
-        let (window, gl) = OpenGLWindow::new(desc, event_loop);
+    pub fn new(
+        desc: &WindowSettings,
+        event_loop: &winit::event_loop::EventLoop<()>,
+    ) -> Result<Renderer, ContextError> {
+        let (window, gl) = OpenGLWindow::new(desc, event_loop)?;
 
         let gl = OpenGL::new(gl);
-        let state = UnsafeShared::new(OpenGLState::new(gl));
+        let mut state = UnsafeShared::new(OpenGLState::new(gl));
 
         let texture_atlas = TextureHandle::new(state.clone(), TextureUnit::Atlas);
         let logical_size = window.logical_size();
+        let matrix_bounds = matrix_from_bounds(&logical_size);
+        state.resize(&window.physical_size(), &matrix_bounds);
 
-        Renderer {
+        Ok(Renderer {
             window,
             state: state,
             texture_atlas,
-            matrix_bounds: matrix_from_bounds(&logical_size),
+            matrix_bounds,
             logical_size,
             atlas: TextureAtlas::new(),
             text_cache: TextCache::new(),
-        }
+        })
     }
 
     // ////////////////////////////////////////////////////////
@@ -67,6 +84,52 @@ impl Renderer {
         b
     }
 
+    // ////////////////////////////////////////////////////////
+    // Framebuffer
+    // ////////////////////////////////////////////////////////
+
+    pub fn framebuffer_create(
+        &mut self,
+        width: u32,
+        height: u32,
+        depth: bool,
+    ) -> Result<Framebuffer, FramebufferError> {
+        Framebuffer::new(self.state.clone(), width, height, depth)
+    }
+
+    pub fn framebuffer_begin(&mut self, framebuffer: &Framebuffer) {
+        framebuffer.bind();
+    }
+
+    pub fn framebuffer_end(&mut self) {
+        self.state.framebuffer_unbind();
+    }
+
+    // ////////////////////////////////////////////////////////
+    // Lighting
+    // ////////////////////////////////////////////////////////
+
+    pub fn lighting_create(&mut self) -> Result<Lighting, FramebufferError> {
+        let size = self.window.physical_size();
+        Lighting::new(self.state.clone(), &self.matrix_bounds, size.x as u32, size.y as u32)
+    }
+
+    // ////////////////////////////////////////////////////////
+    // Shader
+    // ////////////////////////////////////////////////////////
+
+    pub fn shader_create(&mut self, vertex: &str, fragment: &str) -> Result<Shader, ShaderError> {
+        Shader::from_source(self.state.clone(), vertex, fragment)
+    }
+
+    pub fn shader_begin(&mut self, shader: &Shader) {
+        shader.begin();
+    }
+
+    pub fn shader_end(&mut self) {
+        self.state.shader_reset();
+    }
+
     // ////////////////////////////////////////////////////////
     // String
     // ////////////////////////////////////////////////////////
@@ -75,30 +138,34 @@ impl Renderer {
         FontToken::new(self.text_cache.add_font_bytes(bytes))
     }
 
-    pub fn text_append(&mut self, descs: &Vec<Text>, output: &mut Vec<Sprite>) {
-        for desc in descs {
-            self.text_cache.rasterize(&mut self.atlas, desc, output);
-        }
+    pub fn text_append(&mut self, descs: &[Text], output: &mut Vec<Sprite>) {
+        self.text_cache.rasterize_all(&mut self.atlas, descs, output);
         self.texture_sync();
     }
 
-    pub fn text_clear(&mut self, descs: &Vec<Text>, output: &mut Vec<Sprite>) {
+    pub fn text_clear(&mut self, descs: &[Text], output: &mut Vec<Sprite>) {
         unsafe { output.set_len(0) };
-        for desc in descs {
-            self.text_cache.rasterize(&mut self.atlas, desc, output);
-        }
+        self.text_cache.rasterize_all(&mut self.atlas, descs, output);
         self.texture_sync();
     }
 
+    pub fn text_measure(&mut self, desc: &Text) -> AABB2D {
+        self.text_cache.measure(desc)
+    }
+
+    pub fn text_generation(&self) -> u64 {
+        self.text_cache.generation()
+    }
+
     // ////////////////////////////////////////////////////////
     // Texture
     // ////////////////////////////////////////////////////////
 
-    pub fn texture_create(&mut self, bytes: &[u8], format: TextureFormat) -> Texture {
-        let image = Image::from_raw(bytes, format);
-        let uv = self.atlas.add(image);
+    pub fn texture_create(&mut self, bytes: &[u8], format: TextureFormat) -> Result<Texture, TextureError> {
+        let image = Image::from_raw(bytes, format)?;
+        let uv = self.atlas.add(image)?;
         self.texture_sync();
-        Texture(uv)
+        Ok(Texture(uv))
     }
 
     pub fn texture_sync(&mut self) {
@@ -111,6 +178,15 @@ impl Renderer {
     // Window
     // ////////////////////////////////////////////////////////
 
+    pub fn window_context_info(&self) -> ContextInfo {
+        ContextInfo {
+            version: self.state.gl.get_string(StringTarget::Version),
+            shading_language_version: self.state.gl.get_string(StringTarget::ShadingLanguageVersion),
+            vendor: self.state.gl.get_string(StringTarget::Vendor),
+            renderer: self.state.gl.get_string(StringTarget::Renderer),
+        }
+    }
+
     pub fn window_check_resize(&mut self) {
         let new_logical_size = self.window.logical_size();
         if self.logical_size != new_logical_size {
@@ -126,18 +202,64 @@ impl Renderer {
 
     pub fn window_swap_buffers(&self) {
         self.window.swap_buffers();
+        self.state.frame_end();
+    }
+
+    pub fn window_capture(&self) -> (Vec<u8>, u32, u32) {
+        let size = self.window.physical_size();
+        let (width, height) = (size.x as u32, size.y as u32);
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        self.state.gl.read_pixels(
+            0,
+            0,
+            width as i32,
+            height as i32,
+            PixelFormat::RGBA,
+            PixelType::UnsignedByte,
+            &mut pixels,
+        );
+        flip_rows(&mut pixels, (width * 4) as usize);
+        (pixels, width, height)
+    }
+
+    pub fn draw_calls(&self) -> u32 {
+        self.state.last_draw_calls()
     }
 
     pub fn window_logical_size(&self) -> Vector2<f32> {
         self.logical_size
     }
 
+    pub fn window_physical_size(&self) -> Vector2<f32> {
+        self.window.physical_size()
+    }
+
+    pub fn window_scale_factor(&self) -> f32 {
+        self.window.scale_factor()
+    }
+
     pub fn window_title(&mut self, title: &str) {
         self.window.set_title(title);
     }
 
     pub fn window_display_mode(&mut self, display_mode: DisplayMode) {
-        self.window.set_display_mode(display_mode);
+        self.window.set_display_mode(&display_mode);
+    }
+
+    pub fn window_cursor_icon(&mut self, cursor_icon: CursorIcon) {
+        self.window.set_cursor_icon(cursor_icon);
+    }
+
+    pub fn window_monitors(&self) -> Vec<MonitorInfo> {
+        self.window.available_monitors()
+    }
+
+    pub fn window_current_monitor(&self) -> Option<MonitorInfo> {
+        self.window.current_monitor()
+    }
+
+    pub fn window_video_modes(&self) -> Vec<VideoMode> {
+        self.window.available_video_modes()
     }
 
     pub fn clear_color(&mut self, clear_color: RGBA8) {
@@ -148,4 +270,49 @@ impl Renderer {
     pub fn clear(&mut self, clear_mode: ClearMode) {
         self.state.gl.clear(clear_mode);
     }
+
+    pub fn mask_begin(&mut self) {
+        self.state.mask_begin();
+    }
+
+    pub fn mask_content(&mut self) {
+        self.state.mask_content();
+    }
+
+    pub fn mask_end(&mut self) {
+        self.state.mask_end();
+    }
+}
+
+/// Reverses the order of the rows in an image, converting between bottom to top rows as OpenGL
+/// reads them and top to bottom rows.
+fn flip_rows(pixels: &mut [u8], stride: usize) {
+    if stride == 0 {
+        return;
+    }
+    let rows = pixels.len() / stride;
+    for row in 0..rows / 2 {
+        let (top, bottom) = pixels.split_at_mut((rows - row - 1) * stride);
+        top[row * stride..(row + 1) * stride].swap_with_slice(&mut bottom[..stride]);
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flip() {
+        let mut pixels = [1, 1, 2, 2, 3, 3];
+        flip_rows(&mut pixels, 2);
+        assert_eq!(pixels, [3, 3, 2, 2, 1, 1]);
+
+        let mut pixels = [1, 2, 3, 4];
+        flip_rows(&mut pixels, 2);
+        assert_eq!(pixels, [3, 4, 1, 2]);
+    }
 }
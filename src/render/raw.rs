@@ -1,3 +1,4 @@
+use crate::types::ShaderError;
 use glow::HasContext;
 
 #[repr(u32)]
@@ -78,6 +79,19 @@ pub enum DepthTest {
     GreaterEqual = glow::GEQUAL,
 }
 
+#[repr(u32)]
+#[derive(Copy, Clone)]
+pub enum StencilOp {
+    Keep = glow::KEEP,
+    Zero = glow::ZERO,
+    Replace = glow::REPLACE,
+    Increment = glow::INCR,
+    IncrementWrap = glow::INCR_WRAP,
+    Decrement = glow::DECR,
+    DecrementWrap = glow::DECR_WRAP,
+    Invert = glow::INVERT,
+}
+
 /// Describes how the screen will be cleared. Can be composed with other clear modes with the binary
 /// or operator. I.e. ClearMode::COLOR | ClearMode::DEPTH.
 pub struct ClearMode(u32);
@@ -141,6 +155,14 @@ pub enum BlendFactor {
 #[derive(Copy, Clone)]
 pub enum TextureUnit {
     Atlas = glow::TEXTURE0,
+    Framebuffer = glow::TEXTURE1,
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone)]
+pub enum FramebufferAttachment {
+    Color0 = glow::COLOR_ATTACHMENT0,
+    DepthStencil = glow::DEPTH_STENCIL_ATTACHMENT,
 }
 
 #[repr(u32)]
@@ -457,28 +479,45 @@ impl OpenGL {
         }
     }
 
-    pub fn shader_program(&self, vertex_shader: &str, fragment_shader: &str) -> resource::Program {
+    pub fn shader_program(
+        &self,
+        vertex_shader: &str,
+        fragment_shader: &str,
+    ) -> Result<resource::Program, ShaderError> {
         unsafe {
             let vertex = self.gl.create_shader(ShaderType::Vertex as u32).unwrap();
             self.gl.shader_source(vertex, vertex_shader);
             self.gl.compile_shader(vertex);
-            self.check_shader(vertex).unwrap();
+            if let Err(log) = self.check_shader(vertex) {
+                self.gl.delete_shader(vertex);
+                return Err(ShaderError::VertexCompile(log));
+            }
 
             let fragment = self.gl.create_shader(ShaderType::Fragment as u32).unwrap();
             self.gl.shader_source(fragment, fragment_shader);
             self.gl.compile_shader(fragment);
-            self.check_shader(fragment).unwrap();
+            if let Err(log) = self.check_shader(fragment) {
+                self.gl.delete_shader(vertex);
+                self.gl.delete_shader(fragment);
+                return Err(ShaderError::FragmentCompile(log));
+            }
 
             let program = self.gl.create_program().unwrap();
             self.gl.attach_shader(program, vertex);
             self.gl.attach_shader(program, fragment);
             self.gl.link_program(program);
-            self.check_program(program).unwrap();
+            let linked = self.check_program(program);
 
             self.gl.delete_shader(vertex);
             self.gl.delete_shader(fragment);
 
-            program
+            match linked {
+                Ok(()) => Ok(program),
+                Err(log) => {
+                    self.gl.delete_program(program);
+                    Err(ShaderError::Link(log))
+                }
+            }
         }
     }
 
@@ -533,6 +572,14 @@ impl OpenGL {
         unsafe { self.gl.uniform_1_i32(location, x) };
     }
 
+    pub fn uniform_1f(&self, location: Option<&resource::UniformLocation>, x: f32) {
+        unsafe { self.gl.uniform_1_f32(location, x) };
+    }
+
+    pub fn uniform_4f(&self, location: Option<&resource::UniformLocation>, x: f32, y: f32, z: f32, w: f32) {
+        unsafe { self.gl.uniform_4_f32(location, x, y, z, w) };
+    }
+
     pub fn create_vertex_array(&self) -> resource::VertexArray {
         unsafe { self.gl.create_vertex_array().unwrap() }
     }
@@ -592,6 +639,17 @@ impl OpenGL {
         };
     }
 
+    pub fn copy_buffer_sub_data(
+        &self,
+        src: BufferBindingTarget,
+        dst: BufferBindingTarget,
+        src_offset: i32,
+        dst_offset: i32,
+        size: i32,
+    ) {
+        unsafe { self.gl.copy_buffer_sub_data(src as u32, dst as u32, src_offset, dst_offset, size) };
+    }
+
     pub fn buffer_sub_data<T: Sized>(&self, target: BufferBindingTarget, data: &[T]) {
         unsafe {
             let len = core::mem::size_of::<T>() * data.len();
@@ -651,6 +709,30 @@ impl OpenGL {
         };
     }
 
+    pub fn tex_image_2d_empty(
+        &self,
+        target: TextureLoadTarget,
+        width: i32,
+        height: i32,
+        internal_format: PixelInternalFormat,
+        format: PixelFormat,
+        ty: PixelType,
+    ) {
+        unsafe {
+            self.gl.tex_image_2d(
+                target as u32,
+                0,
+                internal_format as u32 as i32,
+                width,
+                height,
+                0,
+                format as u32,
+                ty as u32,
+                None,
+            )
+        };
+    }
+
     pub fn tex_parameter_wrap_s(&self, target: TextureParameterTarget, value: TextureWrapValue) {
         unsafe {
             self.gl.tex_parameter_i32(target as u32, TextureParameterName::TextureWrapS as u32, value as i32)
@@ -693,6 +775,10 @@ impl OpenGL {
         unsafe { self.gl.enable(capability as u32) };
     }
 
+    pub fn disable(&self, capability: Capability) {
+        unsafe { self.gl.disable(capability as u32) };
+    }
+
     pub fn clear_color(&self, red: f32, green: f32, blue: f32, alpha: f32) {
         unsafe { self.gl.clear_color(red, green, blue, alpha) };
     }
@@ -701,6 +787,26 @@ impl OpenGL {
         unsafe { self.gl.depth_func(test as u32) };
     }
 
+    pub fn depth_mask(&self, write: bool) {
+        unsafe { self.gl.depth_mask(write) };
+    }
+
+    pub fn color_mask(&self, red: bool, green: bool, blue: bool, alpha: bool) {
+        unsafe { self.gl.color_mask(red, green, blue, alpha) };
+    }
+
+    pub fn stencil_func(&self, test: DepthTest, reference: i32, mask: u32) {
+        unsafe { self.gl.stencil_func(test as u32, reference, mask) };
+    }
+
+    pub fn stencil_op(&self, stencil_fail: StencilOp, depth_fail: StencilOp, pass: StencilOp) {
+        unsafe { self.gl.stencil_op(stencil_fail as u32, depth_fail as u32, pass as u32) };
+    }
+
+    pub fn stencil_mask(&self, mask: u32) {
+        unsafe { self.gl.stencil_mask(mask) };
+    }
+
     pub fn blend_func(&self, src: BlendFactor, dst: BlendFactor) {
         unsafe { self.gl.blend_func(src as u32, dst as u32) };
     }
@@ -709,6 +815,90 @@ impl OpenGL {
         unsafe { self.gl.cull_face(face as u32) };
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn read_pixels(
+        &self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        format: PixelFormat,
+        ty: PixelType,
+        pixels: &mut [u8],
+    ) {
+        unsafe {
+            self.gl.read_pixels(
+                x,
+                y,
+                width,
+                height,
+                format as u32,
+                ty as u32,
+                glow::PixelPackData::Slice(pixels),
+            )
+        };
+    }
+
+    pub fn create_framebuffer(&self) -> resource::Framebuffer {
+        unsafe { self.gl.create_framebuffer().unwrap() }
+    }
+
+    pub fn bind_framebuffer(&self, framebuffer: Option<resource::Framebuffer>) {
+        unsafe { self.gl.bind_framebuffer(glow::FRAMEBUFFER, framebuffer) };
+    }
+
+    pub fn delete_framebuffer(&self, framebuffer: resource::Framebuffer) {
+        unsafe { self.gl.delete_framebuffer(framebuffer) };
+    }
+
+    pub fn framebuffer_texture_2d(
+        &self,
+        attachment: FramebufferAttachment,
+        texture: Option<resource::Texture>,
+    ) {
+        unsafe {
+            self.gl.framebuffer_texture_2d(glow::FRAMEBUFFER, attachment as u32, glow::TEXTURE_2D, texture, 0)
+        };
+    }
+
+    pub fn create_renderbuffer(&self) -> resource::Renderbuffer {
+        unsafe { self.gl.create_renderbuffer().unwrap() }
+    }
+
+    pub fn bind_renderbuffer(&self, renderbuffer: Option<resource::Renderbuffer>) {
+        unsafe { self.gl.bind_renderbuffer(glow::RENDERBUFFER, renderbuffer) };
+    }
+
+    pub fn delete_renderbuffer(&self, renderbuffer: resource::Renderbuffer) {
+        unsafe { self.gl.delete_renderbuffer(renderbuffer) };
+    }
+
+    /// Allocates depth and stencil storage for the bound renderbuffer.
+    pub fn renderbuffer_storage_depth_stencil(&self, width: i32, height: i32) {
+        unsafe { self.gl.renderbuffer_storage(glow::RENDERBUFFER, glow::DEPTH24_STENCIL8, width, height) };
+    }
+
+    pub fn framebuffer_renderbuffer(
+        &self,
+        attachment: FramebufferAttachment,
+        renderbuffer: Option<resource::Renderbuffer>,
+    ) {
+        unsafe {
+            self.gl.framebuffer_renderbuffer(
+                glow::FRAMEBUFFER,
+                attachment as u32,
+                glow::RENDERBUFFER,
+                renderbuffer,
+            )
+        };
+    }
+
+    /// Returns FRAMEBUFFER_COMPLETE if the bound framebuffer can be drawn to, otherwise the reason it
+    /// can't be.
+    pub fn check_framebuffer_status(&self) -> u32 {
+        unsafe { self.gl.check_framebuffer_status(glow::FRAMEBUFFER) }
+    }
+
     pub fn viewport(&self, x: i32, y: i32, width: i32, height: i32) {
         unsafe { self.gl.viewport(x, y, width, height) };
     }
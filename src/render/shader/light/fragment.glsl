@@ -0,0 +1,19 @@
+#version 300 es
+precision mediump float;
+
+in vec2 v_uv;
+in vec4 v_color;
+out vec4 a_color;
+
+uniform float falloff;
+
+// Lights are brightest at the center of their sprite, and fade out to nothing at the edge of the
+// circle that fits inside of it. The light's intensity is carried in its alpha.
+void main() {
+    float distance = length(v_uv * 2.0 - 1.0);
+    float attenuation = pow(max(1.0 - distance, 0.0), falloff);
+    if (attenuation <= 0.0) {
+        discard;
+    }
+    a_color = vec4(v_color.rgb, v_color.a * attenuation);
+}
@@ -1 +1,190 @@
+pub mod light;
 pub mod texture;
+
+use crate::render::raw::resource;
+use crate::render::state::SpriteUniforms;
+use crate::render::OpenGLState;
+use crate::types::ShaderError;
+use crate::utility::bad::UnsafeShared;
+use cgmath::*;
+use core::cell::RefCell;
+use hashbrown::HashMap;
+
+/// A compiled and linked shader program. Uniform locations are looked up once per name and cached.
+///
+/// Layers draw with the shader between `Engine::shader_begin` and `Engine::shader_end`. Each sprite
+/// is drawn as an instanced quad, so the vertex shader receives the sprite attributes the built in
+/// shader does: `a_pos` (vec3), `a_size` (vec2), `a_uv` (vec4), `a_color` (vec4), and
+/// `a_rotation` (float), at locations 0 through 4. Layers set the `ortho` (mat4) uniform, and the
+/// `tex[0]` (sampler2D), `outline_color` (vec4), and `outline_size` (float) uniforms if the program
+/// uses them.
+pub struct Shader {
+    state: UnsafeShared<OpenGLState>,
+    program: resource::Program,
+    sprite_uniforms: SpriteUniforms,
+    uniforms: RefCell<HashMap<String, Option<resource::UniformLocation>>>,
+}
+
+impl Shader {
+    /// Compiles the vertex and fragment shader sources and links them into a program. On failure,
+    /// the error holds the info log from the graphics driver.
+    pub(crate) fn from_source(
+        state: UnsafeShared<OpenGLState>,
+        vertex: &str,
+        fragment: &str,
+    ) -> Result<Shader, ShaderError> {
+        let program = state.gl.shader_program(vertex, fragment)?;
+        let sprite_uniforms = SpriteUniforms::new(&state.gl, program);
+        Ok(Shader {
+            state,
+            program,
+            sprite_uniforms,
+            uniforms: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Sets a mat4 uniform. Names that aren't active uniforms in the program are ignored.
+    pub fn uniform_mat4(&self, name: &str, m: &Matrix4<f32>) {
+        self.with_uniform(name, |location| {
+            self.state.gl.uniform_matrix_4fv(location, false, m.as_ref());
+        });
+    }
+
+    /// Sets a float uniform. Names that aren't active uniforms in the program are ignored.
+    pub fn uniform_f32(&self, name: &str, x: f32) {
+        self.with_uniform(name, |location| {
+            self.state.gl.uniform_1f(location, x);
+        });
+    }
+
+    /// Sets a vec4 uniform. Names that aren't active uniforms in the program are ignored.
+    pub fn uniform_vec4(&self, name: &str, v: &Vector4<f32>) {
+        self.with_uniform(name, |location| {
+            self.state.gl.uniform_4f(location, v.x, v.y, v.z, v.w);
+        });
+    }
+
+    /// Sets an int uniform, which is also how samplers are assigned a texture unit. Names that
+    /// aren't active uniforms in the program are ignored.
+    pub fn uniform_i32(&self, name: &str, x: i32) {
+        self.with_uniform(name, |location| {
+            self.state.gl.uniform_1i(location, x);
+        });
+    }
+
+    /// Makes layers draw with this shader.
+    pub(crate) fn begin(&self) {
+        let mut state = self.state.clone();
+        state.shader_use(self.program, &self.sprite_uniforms);
+    }
+
+    /// Binds the program, sets the uniform through the given function, then rebinds the shader the
+    /// layers draw with.
+    fn with_uniform<F: FnOnce(Option<&resource::UniformLocation>)>(&self, name: &str, set: F) {
+        let mut uniforms = self.uniforms.borrow_mut();
+        let location = match uniforms.get(name) {
+            Some(location) => location,
+            None => {
+                let location = self.state.gl.get_uniform_location(self.program, name);
+                uniforms.entry(String::from(name)).or_insert(location)
+            }
+        };
+        self.state.gl.use_program(Some(self.program));
+        set(location.as_ref());
+        self.state.shader_bind();
+    }
+}
+
+impl Drop for Shader {
+    fn drop(&mut self) {
+        if self.state.shader_is_active(self.program) {
+            self.state.shader_reset();
+        }
+        self.state.gl.delete_program(self.program);
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::framebuffer::Framebuffer;
+    use crate::render::headless;
+    use crate::render::layer::Layer;
+    use crate::render::raw::{ClearMode, PixelFormat, PixelType, TextureUnit};
+    use crate::render::texture_handle::TextureHandle;
+    use crate::types::Sprite;
+
+    const RED: &str = "#version 300 es
+precision mediump float;
+out vec4 a_color;
+void main() {
+    a_color = vec4(1.0, 0.0, 0.0, 1.0);
+}";
+
+    #[test]
+    fn compile() {
+        let state = match headless::state() {
+            Some(state) => state,
+            None => return,
+        };
+        assert!(Shader::from_source(state.clone(), texture::VERTEX, RED).is_ok());
+
+        let bogus = "#version 300 es\nvoid main() { not_a_function(); }";
+        match Shader::from_source(state.clone(), texture::VERTEX, bogus) {
+            Err(ShaderError::FragmentCompile(log)) => assert!(!log.is_empty()),
+            Err(error) => panic!("Expected a fragment compile error, got {}", error),
+            Ok(_) => panic!("Expected a fragment compile error"),
+        }
+        match Shader::from_source(state, bogus, RED) {
+            Err(ShaderError::VertexCompile(log)) => assert!(!log.is_empty()),
+            Err(error) => panic!("Expected a vertex compile error, got {}", error),
+            Ok(_) => panic!("Expected a vertex compile error"),
+        }
+    }
+
+    #[test]
+    fn draw() {
+        let mut state = match headless::state() {
+            Some(state) => state,
+            None => return,
+        };
+        let _atlas = TextureHandle::new(state.clone(), TextureUnit::Atlas);
+        let framebuffer = Framebuffer::new(state.clone(), 4, 4, false).unwrap();
+        let (shared, mut layer) = Layer::new(state.clone(), &Matrix4::from_scale(1.0));
+        state.layer_add(shared);
+        layer.set_sprites(&vec![Sprite {
+            pos: Vector3::new(-1.0, -1.0, 0.0),
+            size: Vector2::new(2, 2),
+            ..Sprite::default()
+        }]);
+        let read = |state: &UnsafeShared<OpenGLState>| {
+            let mut pixels = vec![0u8; 4 * 4 * 4];
+            state.gl.read_pixels(0, 0, 4, 4, PixelFormat::RGBA, PixelType::UnsignedByte, &mut pixels);
+            pixels
+        };
+
+        // The built in shader draws the white default texture.
+        framebuffer.bind();
+        state.gl.clear(ClearMode::COLOR);
+        layer.draw();
+        assert!(read(&state).iter().all(|&channel| channel == 255));
+
+        // Between begin and reset, the layer draws with the custom shader.
+        let shader = Shader::from_source(state.clone(), texture::VERTEX, RED).unwrap();
+        shader.begin();
+        state.gl.clear(ClearMode::COLOR);
+        layer.draw();
+        assert!(read(&state).chunks(4).all(|pixel| pixel == [255, 0, 0, 255]));
+
+        // Dropping the active shader returns layers to the built in shader.
+        drop(shader);
+        state.gl.clear(ClearMode::COLOR);
+        layer.draw();
+        assert!(read(&state).iter().all(|&channel| channel == 255));
+        framebuffer.unbind();
+    }
+}
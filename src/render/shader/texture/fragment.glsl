@@ -1,15 +1,54 @@
 #version 300 es
 precision mediump float;
 
+// The largest outline thickness in texels.
+const int MAX_OUTLINE = 8;
+
 in vec2 v_uv;
 in vec4 v_color;
+flat in vec4 v_bounds;
 out vec4 a_color;
 
 uniform sampler2D tex[1];
+uniform vec4 outline_color;
+uniform float outline_size;
+
+// Samples the alpha of the texture, treating everything outside of the sprite's region as
+// transparent so neighboring textures in the atlas don't bleed into the outline.
+float sample_alpha(vec2 uv) {
+    if (uv.x < v_bounds.x || uv.x > v_bounds.y || uv.y < v_bounds.z || uv.y > v_bounds.w) {
+        return 0.0;
+    }
+    return texture(tex[0], uv).a;
+}
+
+// Finds the alpha of the most opaque texel within the outline thickness.
+float outline_alpha() {
+    vec2 texel = 1.0 / vec2(textureSize(tex[0], 0));
+    float alpha = 0.0;
+    for (int i = 1; i <= MAX_OUTLINE; i++) {
+        if (float(i) > outline_size) {
+            break;
+        }
+        vec2 offset = texel * float(i);
+        alpha = max(alpha, sample_alpha(v_uv + vec2(offset.x, 0.0)));
+        alpha = max(alpha, sample_alpha(v_uv - vec2(offset.x, 0.0)));
+        alpha = max(alpha, sample_alpha(v_uv + vec2(0.0, offset.y)));
+        alpha = max(alpha, sample_alpha(v_uv - vec2(0.0, offset.y)));
+        alpha = max(alpha, sample_alpha(v_uv + offset));
+        alpha = max(alpha, sample_alpha(v_uv - offset));
+        alpha = max(alpha, sample_alpha(v_uv + vec2(offset.x, -offset.y)));
+        alpha = max(alpha, sample_alpha(v_uv + vec2(-offset.x, offset.y)));
+    }
+    return alpha;
+}
 
 void main() {
     a_color = texture(tex[0], v_uv) * v_color;
+    if (outline_size > 0.0 && a_color.a <= 0.0 && outline_alpha() > 0.0) {
+        a_color = outline_color;
+    }
     if (a_color.a <= 0.0) {
         discard;
     }
-}
\ No newline at end of file
+}
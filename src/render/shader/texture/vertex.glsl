@@ -12,6 +12,7 @@ layout(location = 4) in float a_rotation;
 
 out vec2 v_uv;
 out vec4 v_color;
+flat out vec4 v_bounds;
 
 uniform mat4 ortho;
 
@@ -46,6 +47,7 @@ void main() {
     vec4 temp = a_uv * uv_lut[gl_VertexID];
     v_uv = vec2(temp.x + temp.y, temp.z + temp.w);
     v_color = a_color;
+    v_bounds = vec4(min(a_uv.x, a_uv.y), max(a_uv.x, a_uv.y), min(a_uv.z, a_uv.w), max(a_uv.z, a_uv.w));
 
     vec3 size = vec3(a_size * pos_lut[gl_VertexID], 0.0);
     vec3 pos = a_pos + size;
@@ -0,0 +1,179 @@
+use crate::math::{lerp, AABB2D};
+use crate::render::Layer;
+use crate::types::*;
+use cgmath::prelude::*;
+use cgmath::*;
+
+/// Builds lines, rectangles, and circles out of plain sprites, for debug drawing and simple UI.
+/// Shapes are collected until cleared, and are drawn by syncing them to a layer, so every shape
+/// is drawn in the layer's single draw call. Positions are in the same coordinates as sprites, so
+/// shapes line up with the layer's other sprites. Sprite sizes are whole pixels, so lengths and
+/// thicknesses are rounded to the nearest pixel.
+pub struct ShapeRenderer {
+    depth: f32,
+    sprites: Vec<Sprite>,
+}
+
+impl Default for ShapeRenderer {
+    fn default() -> ShapeRenderer {
+        ShapeRenderer::new()
+    }
+}
+
+impl ShapeRenderer {
+    /// Creates a new renderer with no shapes, drawing at a depth of 0.
+    pub fn new() -> ShapeRenderer {
+        ShapeRenderer {
+            depth: 0.0,
+            sprites: Vec::new(),
+        }
+    }
+
+    /// Sets the depth shapes drawn after this are drawn at. See `Sprite::pos` for how depth orders
+    /// sprites.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth;
+    }
+
+    /// Gets the sprites making up the shapes drawn so far.
+    pub fn sprites(&self) -> &[Sprite] {
+        &self.sprites
+    }
+
+    /// Removes every shape.
+    pub fn clear(&mut self) {
+        self.sprites.clear();
+    }
+
+    /// Sets the layer's sprites to the shapes drawn so far.
+    pub fn sync(&self, layer: &mut Layer) {
+        layer.set_sprites(&self.sprites);
+    }
+
+    /// Draws a line from `a` to `b`. The line is a rectangle of the given thickness centered on the
+    /// segment, so it has the same thickness on every driver.
+    pub fn draw_line(&mut self, a: Vector2<f32>, b: Vector2<f32>, color: RGBA8, thickness: f32) {
+        let delta = b - a;
+        let length = delta.magnitude().round();
+        let thickness = thickness.round();
+        let center = (a + b) / 2.0;
+        let turns = delta.y.atan2(delta.x) / core::f32::consts::TAU;
+        // Sprites rotate around their center, so the unrotated rectangle is centered on the line.
+        let pos = Vector3::new(center.x - length / 2.0, center.y - thickness / 2.0, self.depth);
+        let rotation = if turns < 0.0 {
+            turns + 1.0
+        } else {
+            turns
+        };
+        self.sprites.push(Sprite::new(
+            pos,
+            Vector2::new(length, thickness),
+            Texture::default(),
+            color,
+            rotation,
+        ));
+    }
+
+    /// Draws a filled rectangle.
+    pub fn draw_rect(&mut self, rect: AABB2D, color: RGBA8) {
+        let size = (rect.max - rect.min).map(f32::round);
+        let pos = rect.min.extend(self.depth);
+        self.sprites.push(Sprite::new(pos, size, Texture::default(), color, 0.0));
+    }
+
+    /// Draws a filled rectangle that fades from the top color to the bottom color. Sprites have a
+    /// single color, so the gradient is drawn as one sprite per pixel of the rectangle's height.
+    pub fn draw_gradient_rect(&mut self, rect: AABB2D, top: RGBA8, bottom: RGBA8) {
+        let rows = (rect.max.y - rect.min.y).round().max(0.0) as u32;
+        let (top, bottom): (Vector4<f32>, Vector4<f32>) = (top.into(), bottom.into());
+        for row in 0..rows {
+            // Each row takes the color at its middle.
+            let color = lerp(bottom, top, (row as f32 + 0.5) / rows as f32);
+            let y = rect.min.y + row as f32;
+            self.draw_rect(
+                AABB2D::new(rect.min.x, y, rect.max.x, y + 1.0),
+                RGBA8::new(color.x, color.y, color.z, color.w),
+            );
+        }
+    }
+
+    /// Draws the outline of a rectangle. The outline is drawn inside of the rectangle's bounds.
+    pub fn draw_rect_outline(&mut self, rect: AABB2D, color: RGBA8, thickness: f32) {
+        let t = thickness.round();
+        let (min, max) = (rect.min, rect.max);
+        self.draw_rect(AABB2D::new(min.x, min.y, max.x, min.y + t), color);
+        self.draw_rect(AABB2D::new(min.x, max.y - t, max.x, max.y), color);
+        self.draw_rect(AABB2D::new(min.x, min.y + t, min.x + t, max.y - t), color);
+        self.draw_rect(AABB2D::new(max.x - t, min.y + t, max.x, max.y - t), color);
+    }
+
+    /// Draws a filled circle as a stack of horizontal strips. More segments make a smoother edge,
+    /// at the cost of one sprite per segment.
+    pub fn draw_circle(&mut self, center: Vector2<f32>, radius: f32, segments: u32, color: RGBA8) {
+        if segments == 0 {
+            return;
+        }
+        let strip = radius * 2.0 / segments as f32;
+        for index in 0..segments {
+            let bottom = -radius + strip * index as f32;
+            let top = bottom + strip;
+            // The strip is as wide as the circle at the middle of the strip.
+            let middle = (bottom + top) / 2.0;
+            let half_width = (radius * radius - middle * middle).max(0.0).sqrt();
+            self.draw_rect(
+                AABB2D::new(
+                    center.x - half_width,
+                    center.y + bottom.round(),
+                    center.x + half_width,
+                    center.y + top.round(),
+                ),
+                color,
+            );
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes() {
+        let mut shapes = ShapeRenderer::new();
+        shapes.set_depth(0.5);
+        shapes.draw_line(Vector2::new(0.0, 0.0), Vector2::new(0.0, 10.0), colors::RED, 2.0);
+        let line = shapes.sprites()[0];
+        assert_eq!(line.pos, Vector3::new(-5.0, 4.0, 0.5));
+        assert_eq!(line.size, Vector2::new(10, 2));
+        assert_eq!(line.rotation, 16384);
+
+        shapes.clear();
+        shapes.draw_rect_outline(AABB2D::new(0.0, 0.0, 10.0, 20.0), colors::RED, 1.0);
+        let sizes: Vec<_> = shapes.sprites().iter().map(|sprite| sprite.size).collect();
+        assert_eq!(
+            sizes,
+            vec![Vector2::new(10, 1), Vector2::new(10, 1), Vector2::new(1, 18), Vector2::new(1, 18)]
+        );
+
+        shapes.clear();
+        shapes.draw_gradient_rect(AABB2D::new(0.0, 0.0, 10.0, 4.0), colors::WHITE, colors::BLACK);
+        let sprites = shapes.sprites();
+        assert_eq!(sprites.len(), 4);
+        assert!(sprites.iter().all(|sprite| sprite.size == Vector2::new(10, 1)));
+        assert_eq!(sprites[0].pos.y, 0.0);
+        assert_eq!(sprites[0].color, RGBA8::new_raw(31, 31, 31, 255));
+        assert_eq!(sprites[3].color, RGBA8::new_raw(223, 223, 223, 255));
+
+        shapes.clear();
+        shapes.draw_circle(Vector2::new(0.0, 0.0), 10.0, 4, colors::RED);
+        let sprites = shapes.sprites();
+        assert_eq!(sprites.len(), 4);
+        assert_eq!(sprites[0].size, sprites[3].size);
+        assert_eq!(sprites[1].size, Vector2::new(19, 5));
+        assert_eq!(sprites[0].pos.y, -10.0);
+    }
+}
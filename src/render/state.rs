@@ -1,15 +1,44 @@
 use super::layer::SharedLayer;
-use super::raw::{resource, BlendFactor, Capability, CullFace, DepthTest, OpenGL, TextureUnit};
+use super::raw::{
+    resource, BlendFactor, Capability, ClearMode, CullFace, DepthTest, OpenGL, StencilOp, TextureUnit,
+};
 use super::shader;
+use crate::types::{BlendMode, RGBA8};
 use crate::utility::bad::UnsafeShared;
 use cgmath::*;
+use core::cell::Cell;
+
+/// Locations of the uniforms layers set when they draw. Programs that don't use one of them have
+/// no location for it, and setting it does nothing.
+#[derive(Clone)]
+pub struct SpriteUniforms {
+    ortho: Option<resource::UniformLocation>,
+    texture: Option<resource::UniformLocation>,
+    outline_color: Option<resource::UniformLocation>,
+    outline_size: Option<resource::UniformLocation>,
+}
+
+impl SpriteUniforms {
+    pub fn new(gl: &OpenGL, program: resource::Program) -> SpriteUniforms {
+        SpriteUniforms {
+            ortho: gl.get_uniform_location(program, "ortho"),
+            texture: gl.get_uniform_location(program, "tex[0]"),
+            outline_color: gl.get_uniform_location(program, "outline_color"),
+            outline_size: gl.get_uniform_location(program, "outline_size"),
+        }
+    }
+}
 
 pub struct OpenGLState {
     pub gl: OpenGL,
     layers: Vec<UnsafeShared<SharedLayer>>,
     program: resource::Program,
-    uniform_ortho: resource::UniformLocation,
-    uniform_texture: resource::UniformLocation,
+    uniforms: SpriteUniforms,
+    active_program: resource::Program,
+    active_uniforms: SpriteUniforms,
+    viewport: Vector2<i32>,
+    draw_calls: Cell<u32>,
+    last_draw_calls: Cell<u32>,
 }
 
 impl OpenGLState {
@@ -24,25 +53,44 @@ impl OpenGLState {
         gl.cull_face(CullFace::Back);
 
         // Setup the shader.
-        let program = gl.shader_program(shader::texture::VERTEX, shader::texture::FRAGMENT);
-        let uniform_ortho = gl.get_uniform_location(program, "ortho").unwrap();
-        let uniform_texture = gl.get_uniform_location(program, "tex[0]").unwrap();
+        let program = gl.shader_program(shader::texture::VERTEX, shader::texture::FRAGMENT).unwrap();
+        let uniforms = SpriteUniforms::new(&gl, program);
 
         let mut state = OpenGLState {
             gl,
             layers: Vec::new(),
             program,
-            uniform_ortho,
-            uniform_texture,
+            uniforms: uniforms.clone(),
+            active_program: program,
+            active_uniforms: uniforms,
+            viewport: Vector2::new(0, 0),
+            draw_calls: Cell::new(0),
+            last_draw_calls: Cell::new(0),
         };
 
         // Bind and configure the shader.
         state.shader_bind();
         state.shader_texture(TextureUnit::Atlas);
+        state.shader_outline(RGBA8::new_raw(0, 0, 0, 0), 0);
 
         state
     }
 
+    /// Counts a draw call made during the current frame.
+    pub fn count_draw_call(&self) {
+        self.draw_calls.set(self.draw_calls.get() + 1);
+    }
+
+    /// Ends the current frame, keeping its draw call count and starting a new count.
+    pub fn frame_end(&self) {
+        self.last_draw_calls.set(self.draw_calls.replace(0));
+    }
+
+    /// Gets the number of draw calls made during the previous frame.
+    pub fn last_draw_calls(&self) -> u32 {
+        self.last_draw_calls.get()
+    }
+
     /// Subscribes the layer into getting state updates like resizes.
     pub fn layer_add(&mut self, mut layer: UnsafeShared<SharedLayer>) {
         layer.set_index(self.layers.len());
@@ -58,26 +106,113 @@ impl OpenGLState {
     }
 
     pub fn resize(&mut self, physical: &Vector2<f32>, ortho: &Matrix4<f32>) {
-        self.gl.viewport(0, 0, physical.x as i32, physical.y as i32);
+        self.viewport = Vector2::new(physical.x as i32, physical.y as i32);
+        self.gl.viewport(0, 0, self.viewport.x, self.viewport.y);
         for layer in &mut self.layers {
             layer.set_ortho(ortho);
         }
     }
 
-    /// Binds the shader.
-    pub fn shader_bind(&mut self) {
-        self.gl.use_program(Some(self.program));
+    /// Directs draws back into the window, restoring the window's viewport.
+    pub fn framebuffer_unbind(&self) {
+        self.gl.bind_framebuffer(None);
+        self.gl.viewport(0, 0, self.viewport.x, self.viewport.y);
+    }
+
+    /// Starts drawing a mask. Draws write the mask into the stencil buffer instead of the screen.
+    pub fn mask_begin(&mut self) {
+        self.gl.enable(Capability::StencilTest);
+        self.gl.stencil_mask(0xFF);
+        self.gl.clear(ClearMode::STENCIL);
+        self.gl.stencil_func(DepthTest::Always, 1, 0xFF);
+        self.gl.stencil_op(StencilOp::Keep, StencilOp::Keep, StencilOp::Replace);
+        self.gl.color_mask(false, false, false, false);
+        self.gl.depth_mask(false);
+    }
+
+    /// Stops drawing the mask. Draws go to the screen again, but only where the mask was drawn.
+    pub fn mask_content(&mut self) {
+        self.gl.color_mask(true, true, true, true);
+        self.gl.depth_mask(true);
+        self.gl.stencil_mask(0x00);
+        self.gl.stencil_func(DepthTest::Equal, 1, 0xFF);
+    }
+
+    /// Stops masking, restoring the default stencil, color, and depth state.
+    pub fn mask_end(&mut self) {
+        self.gl.color_mask(true, true, true, true);
+        self.gl.depth_mask(true);
+        self.gl.stencil_mask(0xFF);
+        self.gl.stencil_func(DepthTest::Always, 0, 0xFF);
+        self.gl.stencil_op(StencilOp::Keep, StencilOp::Keep, StencilOp::Keep);
+        self.gl.disable(Capability::StencilTest);
+    }
+
+    /// Sets how following draws are blended with what's already been drawn.
+    pub fn blend_mode(&mut self, mode: BlendMode) {
+        match mode {
+            BlendMode::Alpha => {
+                self.gl.enable(Capability::Blend);
+                self.gl.blend_func(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha);
+            }
+            BlendMode::Additive => {
+                self.gl.enable(Capability::Blend);
+                self.gl.blend_func(BlendFactor::SrcAlpha, BlendFactor::One);
+            }
+            BlendMode::Multiply => {
+                self.gl.enable(Capability::Blend);
+                self.gl.blend_func(BlendFactor::DstColor, BlendFactor::Zero);
+            }
+            BlendMode::None => self.gl.disable(Capability::Blend),
+        }
+    }
+
+    /// Binds the shader layers currently draw with.
+    pub fn shader_bind(&self) {
+        self.gl.use_program(Some(self.active_program));
+    }
+
+    /// Makes layers draw with the given program until the shader is reset.
+    pub fn shader_use(&mut self, program: resource::Program, uniforms: &SpriteUniforms) {
+        self.active_program = program;
+        self.active_uniforms = uniforms.clone();
+        self.shader_bind();
+        self.shader_texture(TextureUnit::Atlas);
+    }
+
+    /// Makes layers draw with the built in sprite shader again.
+    pub fn shader_reset(&mut self) {
+        let uniforms = self.uniforms.clone();
+        self.shader_use(self.program, &uniforms);
+    }
+
+    /// Gets the shader layers currently draw with, so it can be restored with `shader_use`.
+    pub fn shader_active(&self) -> (resource::Program, SpriteUniforms) {
+        (self.active_program, self.active_uniforms.clone())
+    }
+
+    /// Gets if layers currently draw with the given program.
+    pub fn shader_is_active(&self, program: resource::Program) -> bool {
+        self.active_program == program
     }
 
     /// Updates the ortho uniform in the shader.
     pub fn shader_ortho(&mut self, ortho: &Matrix4<f32>) {
-        self.gl.uniform_matrix_4fv(Some(&self.uniform_ortho), false, ortho.as_ref());
+        self.gl.uniform_matrix_4fv(self.active_uniforms.ortho.as_ref(), false, ortho.as_ref());
+    }
+
+    /// Updates the outline uniforms in the shader. A thickness of 0 disables the outline.
+    pub fn shader_outline(&mut self, color: RGBA8, thickness: u8) {
+        let color: Vector4<f32> = color.into();
+        let uniforms = &self.active_uniforms;
+        self.gl.uniform_4f(uniforms.outline_color.as_ref(), color.x, color.y, color.z, color.w);
+        self.gl.uniform_1f(uniforms.outline_size.as_ref(), thickness as f32);
     }
 
     /// Updates the texture uniform in the shader.
     pub fn shader_texture(&mut self, unit: TextureUnit) {
         let unit = (unit as u32 - TextureUnit::Atlas as u32) as i32;
-        self.gl.uniform_1i(Some(&self.uniform_texture), unit);
+        self.gl.uniform_1i(self.active_uniforms.texture.as_ref(), unit);
     }
 }
 
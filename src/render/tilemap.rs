@@ -0,0 +1,250 @@
+use crate::math::AABB2D;
+use crate::render::Layer;
+use crate::types::*;
+use cgmath::*;
+
+const NONE: u32 = u32::MAX;
+
+/// A grid of tiles drawn with textures from a tileset. The tilemap caches the sprites for the
+/// visible tiles and only uploads them to a layer when they change. Tile (0, 0) is the bottom left
+/// tile, with X increasing to the right and Y increasing upwards.
+pub struct Tilemap {
+    width: u32,
+    height: u32,
+    tile_size: Vector2<u16>,
+    origin: Vector3<f32>,
+    tileset: Vec<Texture>,
+    tiles: Vec<Option<u16>>,
+    view: Option<AABB2D>,
+    sprites: Vec<Sprite>,
+    lookup: Vec<u32>,
+    rebuild: bool,
+    upload: bool,
+}
+
+impl Tilemap {
+    /// Creates a new empty tilemap. The tile size is measured in pixels, and tiles reference
+    /// textures in the tileset by index.
+    pub fn new(width: u32, height: u32, tile_size: Vector2<u16>, tileset: Vec<Texture>) -> Tilemap {
+        let cells = (width * height) as usize;
+        Tilemap {
+            width,
+            height,
+            tile_size,
+            origin: Vector3::new(0.0, 0.0, 0.0),
+            tileset,
+            tiles: vec![None; cells],
+            view: None,
+            sprites: Vec::new(),
+            lookup: vec![NONE; cells],
+            rebuild: true,
+            upload: true,
+        }
+    }
+
+    /// The width of the tilemap in tiles.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The height of the tilemap in tiles.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Sets the position of the bottom left corner of the tilemap. The Z coordinate represents the
+    /// depth of every tile. Units are measured in pixels.
+    pub fn set_origin(&mut self, origin: Vector3<f32>) {
+        self.origin = origin;
+        self.rebuild = true;
+    }
+
+    /// Sets the region that's visible, in the same coordinates as the origin. Tiles outside of the
+    /// region are culled. If this is set to None, every tile is drawn. The default is None.
+    pub fn set_view(&mut self, view: Option<AABB2D>) {
+        self.view = view;
+        self.rebuild = true;
+    }
+
+    /// Gets the tileset index of the tile at the given coordinate.
+    pub fn tile(&self, x: u32, y: u32) -> Option<u16> {
+        self.tiles[self.index_for(x, y)]
+    }
+
+    /// Sets the tile at the given coordinate to an index into the tileset, or None to clear it.
+    /// Replacing one visible tile with another only updates that tile instead of rebuilding the
+    /// whole map.
+    pub fn set_tile(&mut self, x: u32, y: u32, tile: Option<u16>) {
+        let index = self.index_for(x, y);
+        let previous = self.tiles[index];
+        self.tiles[index] = tile;
+        if self.rebuild {
+            return;
+        }
+        let sprite = self.lookup[index];
+        match (previous, tile) {
+            (Some(_), Some(tile)) => {
+                // Tiles that were culled have no sprite, and remain culled.
+                if sprite != NONE {
+                    self.sprites[sprite as usize].texture = self.tileset[tile as usize];
+                    self.upload = true;
+                }
+            }
+            (None, None) => {}
+            _ => self.rebuild = true,
+        }
+    }
+
+    /// Uploads the tiles to the layer if they've changed since the last sync.
+    pub fn sync(&mut self, layer: &mut Layer) {
+        if self.rebuild {
+            self.build();
+        }
+        if self.upload {
+            self.upload = false;
+            layer.set_sprites(&self.sprites);
+        }
+    }
+
+    fn build(&mut self) {
+        self.rebuild = false;
+        self.upload = true;
+        self.sprites.clear();
+        for value in self.lookup.iter_mut() {
+            *value = NONE;
+        }
+
+        let (min, max) = self.visible_range();
+        let size = Vector2::new(self.tile_size.x as f32, self.tile_size.y as f32);
+        for y in min.y..max.y {
+            for x in min.x..max.x {
+                let index = self.index_for(x, y);
+                if let Some(tile) = self.tiles[index] {
+                    let pos = Vector3::new(
+                        self.origin.x + x as f32 * size.x,
+                        self.origin.y + y as f32 * size.y,
+                        self.origin.z,
+                    );
+                    self.lookup[index] = self.sprites.len() as u32;
+                    self.sprites.push(Sprite::new_raw(
+                        pos,
+                        self.tile_size,
+                        self.tileset[tile as usize],
+                        colors::WHITE,
+                        0,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Gets the range of tile coordinates overlapping the view, with an exclusive upper bound.
+    fn visible_range(&self) -> (Vector2<u32>, Vector2<u32>) {
+        let full = (Vector2::new(0, 0), Vector2::new(self.width, self.height));
+        let view = match self.view {
+            Some(view) => view,
+            None => return full,
+        };
+        let size = Vector2::new(self.tile_size.x as f32, self.tile_size.y as f32);
+        let clamp = |value: f32, max: u32| value.max(0.0).min(max as f32) as u32;
+        let min = Vector2::new(
+            clamp(((view.min.x - self.origin.x) / size.x).floor(), self.width),
+            clamp(((view.min.y - self.origin.y) / size.y).floor(), self.height),
+        );
+        let max = Vector2::new(
+            clamp(((view.max.x - self.origin.x) / size.x).ceil(), self.width),
+            clamp(((view.max.y - self.origin.y) / size.y).ceil(), self.height),
+        );
+        (min, max)
+    }
+
+    #[inline(always)]
+    fn index_for(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "Tile coordinate is outside of the tilemap.");
+        (y * self.width + x) as usize
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tileset() -> Vec<Texture> {
+        (0..3).map(|index| Texture(Vector4::new(index, index, index, index))).collect()
+    }
+
+    #[test]
+    fn build() {
+        let mut map = Tilemap::new(3, 2, Vector2::new(16, 8), tileset());
+        map.set_origin(Vector3::new(100.0, 50.0, 0.5));
+        map.set_tile(0, 0, Some(1));
+        map.set_tile(2, 1, Some(2));
+        map.build();
+        assert_eq!(map.sprites.len(), 2);
+        assert_eq!(map.sprites[0].pos, Vector3::new(100.0, 50.0, 0.5));
+        assert_eq!(map.sprites[0].size, Vector2::new(16, 8));
+        assert_eq!(map.sprites[0].texture, tileset()[1]);
+        assert_eq!(map.sprites[1].pos, Vector3::new(132.0, 58.0, 0.5));
+        assert_eq!(map.lookup[map.index_for(2, 1)], 1);
+        assert_eq!(map.lookup[map.index_for(1, 0)], NONE);
+    }
+
+    #[test]
+    fn visible_range() {
+        let mut map = Tilemap::new(10, 10, Vector2::new(10, 10), tileset());
+        assert_eq!(map.visible_range(), (Vector2::new(0, 0), Vector2::new(10, 10)));
+
+        // Partially covered tiles are included.
+        map.set_view(Some(AABB2D::new(15.0, 20.0, 35.0, 41.0)));
+        assert_eq!(map.visible_range(), (Vector2::new(1, 2), Vector2::new(4, 5)));
+
+        // The range is clamped to the map, and the origin moves it.
+        map.set_view(Some(AABB2D::new(-50.0, 95.0, 50.0, 500.0)));
+        assert_eq!(map.visible_range(), (Vector2::new(0, 9), Vector2::new(5, 10)));
+        map.set_origin(Vector3::new(20.0, 0.0, 0.0));
+        assert_eq!(map.visible_range(), (Vector2::new(0, 9), Vector2::new(3, 10)));
+
+        // Culled tiles don't get sprites.
+        map.set_view(Some(AABB2D::new(0.0, 0.0, 10.0, 10.0)));
+        map.set_origin(Vector3::new(0.0, 0.0, 0.0));
+        map.set_tile(0, 0, Some(0));
+        map.set_tile(5, 5, Some(0));
+        map.build();
+        assert_eq!(map.sprites.len(), 1);
+    }
+
+    #[test]
+    fn set_tile() {
+        let mut map = Tilemap::new(4, 4, Vector2::new(10, 10), tileset());
+        map.set_view(Some(AABB2D::new(0.0, 0.0, 20.0, 20.0)));
+        map.set_tile(0, 0, Some(0));
+        map.set_tile(1, 1, Some(0));
+        map.set_tile(3, 3, Some(0));
+        map.build();
+        map.upload = false;
+
+        // Replacing a visible tile updates its sprite in place.
+        map.set_tile(1, 1, Some(2));
+        assert!(!map.rebuild && map.upload);
+        assert_eq!(map.sprites[1].texture, tileset()[2]);
+
+        // Replacing a culled tile changes nothing that's drawn.
+        map.upload = false;
+        map.set_tile(3, 3, Some(1));
+        assert!(!map.rebuild && !map.upload);
+        assert_eq!(map.tile(3, 3), Some(1));
+
+        // Adding or removing a tile needs a rebuild.
+        map.set_tile(0, 1, Some(1));
+        assert!(map.rebuild);
+        map.build();
+        map.set_tile(0, 0, None);
+        assert!(map.rebuild);
+        map.build();
+        assert_eq!(map.sprites.len(), 2);
+    }
+}
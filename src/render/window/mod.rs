@@ -7,3 +7,122 @@ pub use self::native::OpenGLWindow;
 mod wasm;
 #[cfg(target_arch = "wasm32")]
 pub use self::wasm::OpenGLWindow;
+
+use crate::types::{CursorIcon, MonitorInfo, VideoMode};
+use cgmath::*;
+use winit::monitor::MonitorHandle;
+use winit::window::Fullscreen;
+
+/// Converts the windowing layer's monitor into the engine's monitor description. The refresh rate
+/// is taken from the fastest video mode matching the monitor's resolution.
+fn monitor_info(handle: &MonitorHandle) -> MonitorInfo {
+    let size = handle.size();
+    let position = handle.position();
+    let refresh_rate = handle
+        .video_modes()
+        .filter(|mode| mode.size() == size)
+        .map(|mode| mode.refresh_rate())
+        .max()
+        .unwrap_or(0);
+    MonitorInfo {
+        name: handle.name().unwrap_or_default(),
+        position: Vector2::new(position.x, position.y),
+        size: Vector2::new(size.width, size.height),
+        refresh_rate,
+        scale_factor: handle.scale_factor() as f32,
+    }
+}
+
+/// Converts the engine's cursor icon into the windowing layer's cursor icon.
+fn cursor_icon(cursor_icon: CursorIcon) -> winit::window::CursorIcon {
+    match cursor_icon {
+        CursorIcon::Default => winit::window::CursorIcon::Default,
+        CursorIcon::Crosshair => winit::window::CursorIcon::Crosshair,
+        CursorIcon::Hand => winit::window::CursorIcon::Hand,
+        CursorIcon::Arrow => winit::window::CursorIcon::Arrow,
+        CursorIcon::Move => winit::window::CursorIcon::Move,
+        CursorIcon::Text => winit::window::CursorIcon::Text,
+        CursorIcon::Wait => winit::window::CursorIcon::Wait,
+        CursorIcon::Help => winit::window::CursorIcon::Help,
+        CursorIcon::Progress => winit::window::CursorIcon::Progress,
+        CursorIcon::NotAllowed => winit::window::CursorIcon::NotAllowed,
+        CursorIcon::ContextMenu => winit::window::CursorIcon::ContextMenu,
+        CursorIcon::Cell => winit::window::CursorIcon::Cell,
+        CursorIcon::VerticalText => winit::window::CursorIcon::VerticalText,
+        CursorIcon::Alias => winit::window::CursorIcon::Alias,
+        CursorIcon::Copy => winit::window::CursorIcon::Copy,
+        CursorIcon::NoDrop => winit::window::CursorIcon::NoDrop,
+        CursorIcon::Grab => winit::window::CursorIcon::Grab,
+        CursorIcon::Grabbing => winit::window::CursorIcon::Grabbing,
+        CursorIcon::AllScroll => winit::window::CursorIcon::AllScroll,
+        CursorIcon::ZoomIn => winit::window::CursorIcon::ZoomIn,
+        CursorIcon::ZoomOut => winit::window::CursorIcon::ZoomOut,
+        CursorIcon::EResize => winit::window::CursorIcon::EResize,
+        CursorIcon::NResize => winit::window::CursorIcon::NResize,
+        CursorIcon::NeResize => winit::window::CursorIcon::NeResize,
+        CursorIcon::NwResize => winit::window::CursorIcon::NwResize,
+        CursorIcon::SResize => winit::window::CursorIcon::SResize,
+        CursorIcon::SeResize => winit::window::CursorIcon::SeResize,
+        CursorIcon::SwResize => winit::window::CursorIcon::SwResize,
+        CursorIcon::WResize => winit::window::CursorIcon::WResize,
+        CursorIcon::EwResize => winit::window::CursorIcon::EwResize,
+        CursorIcon::NsResize => winit::window::CursorIcon::NsResize,
+        CursorIcon::NeswResize => winit::window::CursorIcon::NeswResize,
+        CursorIcon::NwseResize => winit::window::CursorIcon::NwseResize,
+        CursorIcon::ColResize => winit::window::CursorIcon::ColResize,
+        CursorIcon::RowResize => winit::window::CursorIcon::RowResize,
+    }
+}
+
+/// Lists the video modes of every monitor in the windowing layer's monitor list.
+fn video_modes<I: Iterator<Item = MonitorHandle>>(monitors: I) -> Vec<VideoMode> {
+    let mut output = Vec::new();
+    for handle in monitors {
+        let monitor = handle.name().unwrap_or_default();
+        for mode in handle.video_modes() {
+            let size = mode.size();
+            output.push(VideoMode {
+                monitor: monitor.clone(),
+                size: Vector2::new(size.width, size.height),
+                bit_depth: mode.bit_depth(),
+                refresh_rate: mode.refresh_rate(),
+            });
+        }
+    }
+    output
+}
+
+/// Selects the monitor at the index in the windowing layer's monitor list, which is the same order
+/// as `Engine::window_monitors`. Falls back to the primary monitor if the index is out of range.
+fn select_monitor<I: Iterator<Item = MonitorHandle>>(
+    mut monitors: I,
+    index: usize,
+    primary_monitor: Option<MonitorHandle>,
+) -> Option<MonitorHandle> {
+    monitors.nth(index).or(primary_monitor)
+}
+
+/// Gets exclusive fullscreen on the monitor using the requested video mode. Without a requested
+/// mode, or if the monitor doesn't support it, the monitor's current resolution is used at its
+/// fastest refresh rate so the resolution doesn't change when entering fullscreen. If the monitor
+/// doesn't report a video mode at its current resolution, borderless fullscreen is used instead.
+fn exclusive_fullscreen(monitor: Option<MonitorHandle>, video_mode: Option<&VideoMode>) -> Fullscreen {
+    let mode = monitor.as_ref().and_then(|monitor| {
+        let requested = video_mode.and_then(|video_mode| {
+            monitor.video_modes().find(|mode| {
+                let size = mode.size();
+                Vector2::new(size.width, size.height) == video_mode.size
+                    && mode.bit_depth() == video_mode.bit_depth
+                    && mode.refresh_rate() == video_mode.refresh_rate
+            })
+        });
+        requested.or_else(|| {
+            let size = monitor.size();
+            monitor.video_modes().filter(|mode| mode.size() == size).max_by_key(|mode| mode.refresh_rate())
+        })
+    });
+    match mode {
+        Some(mode) => Fullscreen::Exclusive(mode),
+        None => Fullscreen::Borderless(monitor),
+    }
+}
@@ -1,8 +1,10 @@
+use super::{cursor_icon, exclusive_fullscreen, monitor_info, select_monitor, video_modes};
 use crate::types::*;
 use cgmath::*;
-use glutin::ContextBuilder;
+use glutin::{Api, ContextBuilder, GlRequest};
 use winit::dpi::LogicalSize;
 use winit::event_loop::EventLoop;
+use winit::monitor::MonitorHandle;
 use winit::window::{Fullscreen, Window, WindowBuilder};
 
 pub struct OpenGLWindow {
@@ -10,23 +12,26 @@ pub struct OpenGLWindow {
 }
 
 impl OpenGLWindow {
-    pub fn new(desc: &WindowSettings, event_loop: &EventLoop<()>) -> (OpenGLWindow, glow::Context) {
-        let mut window_builder = WindowBuilder::new().with_title(&desc.title);
-        match desc.display_mode {
-            DisplayMode::Windowed {
-                width,
-                height,
-                resizable,
-            } => {
-                window_builder =
-                    window_builder.with_resizable(resizable).with_inner_size(LogicalSize::new(width, height))
+    pub fn new(
+        desc: &WindowSettings,
+        event_loop: &EventLoop<()>,
+    ) -> Result<(OpenGLWindow, glow::Context), ContextError> {
+        let window_builder =
+            window_builder(desc, event_loop.available_monitors(), event_loop.primary_monitor());
+        let mut context_builder =
+            ContextBuilder::new().with_stencil_buffer(8).with_gl_debug_flag(desc.context.debug);
+        if let Some((major, minor)) = desc.context.gl_version {
+            context_builder = context_builder.with_gl(GlRequest::Specific(Api::OpenGl, (major, minor)));
+        }
+        match desc.context.gl_profile {
+            GlProfile::Any => {}
+            GlProfile::Core => {
+                context_builder = context_builder.with_gl_profile(glutin::GlProfile::Core);
             }
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
-                let fullscreen = Fullscreen::Borderless(event_loop.primary_monitor());
-                window_builder = window_builder.with_fullscreen(Some(fullscreen));
+            GlProfile::Compatibility => {
+                context_builder = context_builder.with_gl_profile(glutin::GlProfile::Compatibility);
             }
         }
-        let mut context_builder = ContextBuilder::new();
         match desc.vsync {
             Vsync::Disabled => {
                 context_builder = context_builder.with_vsync(false);
@@ -35,18 +40,21 @@ impl OpenGLWindow {
                 context_builder = context_builder.with_vsync(true);
             }
         }
-        let window_context = context_builder.build_windowed(window_builder, &event_loop).unwrap();
-        let window_context = unsafe { window_context.make_current() }.unwrap();
+        let window_context = context_builder
+            .build_windowed(window_builder, &event_loop)
+            .map_err(|error| ContextError::new(format!("{} (requested {:?})", error, desc.context)))?;
+        let window_context = unsafe { window_context.make_current() }
+            .map_err(|(_, error)| ContextError::new(format!("{:?}", error)))?;
         let gl = unsafe {
             glow::Context::from_loader_function(|s| window_context.get_proc_address(s) as *const _)
         };
         info!("Created window.");
-        (
+        Ok((
             OpenGLWindow {
                 inner: window_context,
             },
             gl,
-        )
+        ))
     }
 
     #[inline]
@@ -64,6 +72,11 @@ impl OpenGLWindow {
         Vector2::new(size.width as f32, size.height as f32)
     }
 
+    #[inline]
+    pub fn scale_factor(&self) -> f32 {
+        self.inner.window().scale_factor() as f32
+    }
+
     /// Swaps the buffers in case of double or triple buffering. You should
     /// call this function every time you have finished rendering, or the
     /// image may not be displayed on the screen.
@@ -76,21 +89,125 @@ impl OpenGLWindow {
         self.inner.window().set_title(title);
     }
 
-    pub fn set_display_mode(&self, display_mode: DisplayMode) {
+    pub fn set_display_mode(&self, display_mode: &DisplayMode) {
+        let window = self.inner.window();
         match display_mode {
             DisplayMode::Windowed {
                 width,
                 height,
                 resizable,
             } => {
-                self.inner.window().set_inner_size(LogicalSize::new(width, height));
-                self.inner.window().set_resizable(resizable);
-                self.inner.window().set_fullscreen(None);
+                window.set_inner_size(LogicalSize::new(*width, *height));
+                window.set_resizable(*resizable);
+                window.set_fullscreen(None);
             }
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
-                let fullscreen = Fullscreen::Borderless(self.inner.window().primary_monitor());
-                self.inner.window().set_fullscreen(Some(fullscreen));
+            DisplayMode::WindowedFullscreen => {
+                let fullscreen = Fullscreen::Borderless(window.primary_monitor());
+                window.set_fullscreen(Some(fullscreen));
             }
+            DisplayMode::Fullscreen {
+                monitor,
+                video_mode,
+            } => {
+                let monitor = select_monitor(window.available_monitors(), *monitor, window.primary_monitor());
+                let fullscreen = exclusive_fullscreen(monitor, video_mode.as_ref());
+                window.set_fullscreen(Some(fullscreen));
+            }
+        }
+    }
+
+    pub fn set_cursor_icon(&self, icon: CursorIcon) {
+        self.inner.window().set_cursor_icon(cursor_icon(icon));
+    }
+
+    pub fn available_monitors(&self) -> Vec<MonitorInfo> {
+        self.inner.window().available_monitors().map(|handle| monitor_info(&handle)).collect()
+    }
+
+    pub fn current_monitor(&self) -> Option<MonitorInfo> {
+        self.inner.window().current_monitor().map(|handle| monitor_info(&handle))
+    }
+
+    pub fn available_video_modes(&self) -> Vec<VideoMode> {
+        video_modes(self.inner.window().available_monitors())
+    }
+}
+
+/// Creates the window builder for the settings. Exclusive fullscreen selects its monitor from the
+/// given monitors, and the primary monitor is used otherwise.
+fn window_builder<I: Iterator<Item = MonitorHandle>>(
+    desc: &WindowSettings,
+    monitors: I,
+    primary_monitor: Option<MonitorHandle>,
+) -> WindowBuilder {
+    let window_builder = WindowBuilder::new().with_title(&desc.title);
+    match &desc.display_mode {
+        DisplayMode::Windowed {
+            width,
+            height,
+            resizable,
+        } => window_builder.with_resizable(*resizable).with_inner_size(LogicalSize::new(*width, *height)),
+        DisplayMode::WindowedFullscreen => {
+            window_builder.with_fullscreen(Some(Fullscreen::Borderless(primary_monitor)))
         }
+        DisplayMode::Fullscreen {
+            monitor,
+            video_mode,
+        } => {
+            let monitor = select_monitor(monitors, *monitor, primary_monitor);
+            window_builder.with_fullscreen(Some(exclusive_fullscreen(monitor, video_mode.as_ref())))
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::dpi::Size;
+
+    #[test]
+    fn builder() {
+        let desc = WindowSettings {
+            title: String::from("Test"),
+            display_mode: DisplayMode::Windowed {
+                width: 1280,
+                height: 720,
+                resizable: false,
+            },
+            ..WindowSettings::default()
+        };
+        let builder = window_builder(&desc, core::iter::empty(), None);
+        assert_eq!(builder.window.title, "Test");
+        assert_eq!(builder.window.inner_size, Some(Size::Logical(LogicalSize::new(1280.0, 720.0))));
+        assert!(!builder.window.resizable);
+        assert_eq!(builder.window.fullscreen, None);
+
+        let desc = WindowSettings {
+            display_mode: DisplayMode::WindowedFullscreen,
+            ..WindowSettings::default()
+        };
+        let builder = window_builder(&desc, core::iter::empty(), None);
+        assert_eq!(builder.window.fullscreen, Some(Fullscreen::Borderless(None)));
+
+        // Without any monitors, an out of range index falls back to the missing primary monitor,
+        // and exclusive fullscreen falls back to borderless.
+        let desc = WindowSettings {
+            display_mode: DisplayMode::Fullscreen {
+                monitor: 3,
+                video_mode: Some(VideoMode {
+                    monitor: String::from("Missing"),
+                    size: Vector2::new(1920, 1080),
+                    bit_depth: 32,
+                    refresh_rate: 60,
+                }),
+            },
+            ..WindowSettings::default()
+        };
+        let builder = window_builder(&desc, core::iter::empty(), None);
+        assert_eq!(builder.window.fullscreen, Some(Fullscreen::Borderless(None)));
     }
 }
@@ -1,3 +1,4 @@
+use super::{cursor_icon, monitor_info, video_modes};
 use crate::types::*;
 use cgmath::*;
 use wasm_bindgen::JsCast;
@@ -11,7 +12,10 @@ pub struct OpenGLWindow {
 }
 
 impl OpenGLWindow {
-    pub fn new(desc: &WindowSettings, event_loop: &EventLoop<()>) -> (OpenGLWindow, glow::Context) {
+    pub fn new(
+        desc: &WindowSettings,
+        event_loop: &EventLoop<()>,
+    ) -> Result<(OpenGLWindow, glow::Context), ContextError> {
         let mut builder = WindowBuilder::new().with_title(&desc.title);
         builder = match desc.display_mode {
             DisplayMode::Windowed {
@@ -19,19 +23,22 @@ impl OpenGLWindow {
                 height,
                 ..
             } => builder.with_inner_size(LogicalSize::new(width, height)),
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
-                builder.with_fullscreen(Some(Fullscreen::Borderless(None)))
-            }
+            DisplayMode::WindowedFullscreen
+            | DisplayMode::Fullscreen {
+                ..
+            } => builder.with_fullscreen(Some(Fullscreen::Borderless(None))),
         };
         let winit_window = builder.build(event_loop).expect("Window build");
 
         let canvas = winit_window.canvas();
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &"stencil".into(), &true.into()).expect("Set stencil option");
         let webgl2_context = canvas
-            .get_context("webgl2") // Result<Option<Object>, JsValue>
-            .expect("Get webgl2 context A") // Option<Object>
-            .expect("Get webgl2 context B") // Object
-            .dyn_into::<web_sys::WebGl2RenderingContext>() // Result<WebGl2RenderingContext, Object>
-            .expect("Get webgl2 context C"); // WebGl2RenderingContext
+            .get_context_with_context_options("webgl2", &options) // Result<Option<Object>, JsValue>
+            .ok()
+            .flatten() // Option<Object>
+            .and_then(|context| context.dyn_into::<web_sys::WebGl2RenderingContext>().ok())
+            .ok_or_else(|| ContextError::new(String::from("WebGL 2 is unavailable")))?;
         let gl = glow::Context::from_webgl2_context(webgl2_context);
 
         let window = web_sys::window().unwrap();
@@ -43,9 +50,9 @@ impl OpenGLWindow {
             inner: winit_window,
         };
         window.set_title(&desc.title);
-        window.set_display_mode(desc.display_mode);
+        window.set_display_mode(&desc.display_mode);
 
-        (window, gl)
+        Ok((window, gl))
     }
 
     pub fn logical_size(&self) -> Vector2<f32> {
@@ -61,6 +68,10 @@ impl OpenGLWindow {
         Vector2::new(size.width as f32, size.height as f32)
     }
 
+    pub fn scale_factor(&self) -> f32 {
+        self.inner.scale_factor() as f32
+    }
+
     pub fn swap_buffers(&self) {
         // This is implicit on web.
     }
@@ -73,7 +84,7 @@ impl OpenGLWindow {
             .set_title(title);
     }
 
-    pub fn set_display_mode(&self, display_mode: DisplayMode) {
+    pub fn set_display_mode(&self, display_mode: &DisplayMode) {
         match display_mode {
             DisplayMode::Windowed {
                 width,
@@ -83,11 +94,30 @@ impl OpenGLWindow {
                 if let Some(_) = self.inner.fullscreen() {
                     self.inner.set_fullscreen(None);
                 }
-                self.inner.set_inner_size(LogicalSize::new(width, height));
+                self.inner.set_inner_size(LogicalSize::new(*width, *height));
             }
-            DisplayMode::WindowedFullscreen | DisplayMode::Fullscreen => {
+            DisplayMode::WindowedFullscreen
+            | DisplayMode::Fullscreen {
+                ..
+            } => {
                 self.inner.set_fullscreen(Some(Fullscreen::Borderless(None)));
             }
         }
     }
+
+    pub fn set_cursor_icon(&self, icon: CursorIcon) {
+        self.inner.set_cursor_icon(cursor_icon(icon));
+    }
+
+    pub fn available_monitors(&self) -> Vec<MonitorInfo> {
+        self.inner.available_monitors().map(|handle| monitor_info(&handle)).collect()
+    }
+
+    pub fn current_monitor(&self) -> Option<MonitorInfo> {
+        self.inner.current_monitor().map(|handle| monitor_info(&handle))
+    }
+
+    pub fn available_video_modes(&self) -> Vec<VideoMode> {
+        video_modes(self.inner.available_monitors())
+    }
 }
@@ -1,30 +1,100 @@
+use crate::math::AABB2D;
 use crate::texture::*;
 use crate::*;
 use cgmath::*;
 use fontdue::layout::{
-    CoordinateSystem, GlyphRasterConfig, HorizontalAlign, Layout, LayoutSettings, TextStyle, VerticalAlign,
+    CoordinateSystem, GlyphPosition, GlyphRasterConfig, Layout, LayoutSettings, TextStyle,
 };
 use fontdue::{Font, FontSettings};
 use hashbrown::HashMap;
 
+/// The width and height in pixels of a page of glyphs in the atlas.
+const PAGE_SIZE: u32 = 1024;
+/// The most pages glyphs can use before they're evicted.
+const MAX_PAGES: usize = 2;
+
 #[derive(Debug, Copy, Clone)]
 struct CharCacheValue {
     uv: Vector4<u16>,
     size: Vector2<f32>,
 }
 
+/// Regions of the atlas reserved for glyphs. Pages are reserved as they're needed, and once the
+/// limit is reached and every page is full, all of the pages are emptied to make room.
+struct GlyphPages {
+    size: u32,
+    max_pages: usize,
+    pages: Vec<(Rect, Packer)>,
+    generation: u64,
+}
+
+impl GlyphPages {
+    fn new(size: u32, max_pages: usize) -> GlyphPages {
+        GlyphPages {
+            size,
+            max_pages,
+            pages: Vec::new(),
+            generation: 0,
+        }
+    }
+
+    /// Adds the glyph to a page, returning its uv. Returns None if the glyph is larger than a
+    /// page, or if the atlas has no room left for even one page. If the pages had to be emptied to
+    /// fit the glyph, the generation is increased.
+    fn add(&mut self, atlas: &mut TextureAtlas, image: &Image) -> Option<Vector4<u16>> {
+        let (width, height) = (image.width(), image.height());
+        if width > self.size || height > self.size {
+            warn!("Glyph is too large for the glyph cache.");
+            return None;
+        }
+        for (bounds, packer) in &mut self.pages {
+            if let Some(rect) = packer.pack(width, height) {
+                return Some(atlas.set(bounds.x + rect.x, bounds.y + rect.y, image));
+            }
+        }
+        // Every page is full, so reserve another one if allowed, otherwise evict every glyph.
+        if self.pages.len() < self.max_pages {
+            if let Some(bounds) = atlas.reserve(self.size, self.size) {
+                let mut packer = Packer::new(self.size, self.size);
+                let rect = packer.pack(width, height)?;
+                self.pages.push((bounds, packer));
+                return Some(atlas.set(bounds.x + rect.x, bounds.y + rect.y, image));
+            }
+        }
+        if self.pages.is_empty() {
+            warn!("Unable to fit the glyph cache into the atlas, skipping the glyph.");
+            return None;
+        }
+        self.generation += 1;
+        for (_, packer) in &mut self.pages {
+            *packer = Packer::new(self.size, self.size);
+        }
+        let (bounds, packer) = &mut self.pages[0];
+        let rect = packer.pack(width, height)?;
+        Some(atlas.set(bounds.x + rect.x, bounds.y + rect.y, image))
+    }
+}
+
 pub struct TextCache {
     cache: HashMap<GlyphRasterConfig, CharCacheValue>,
+    pages: GlyphPages,
     layout: Layout,
+    glyphs: Vec<GlyphPosition>,
     fonts: Vec<Font>,
     dirty: bool,
 }
 
 impl TextCache {
     pub fn new() -> TextCache {
+        TextCache::with_pages(PAGE_SIZE, MAX_PAGES)
+    }
+
+    fn with_pages(page_size: u32, max_pages: usize) -> TextCache {
         let mut manager = TextCache {
             cache: HashMap::new(),
+            pages: GlyphPages::new(page_size, max_pages),
             layout: Layout::new(CoordinateSystem::PositiveYUp),
+            glyphs: Vec::new(),
             fonts: Vec::new(),
             dirty: true,
         };
@@ -51,41 +121,92 @@ impl TextCache {
     //     self.add_font_bytes(&bytes)
     // }
 
+    /// Gets the generation of the glyph cache, which increases every time the cached glyphs are
+    /// evicted.
+    pub fn generation(&self) -> u64 {
+        self.pages.generation
+    }
+
+    /// Rasterizes every text into sprites, appending them to the output. If glyphs are evicted part
+    /// way through, sprites from earlier text would reference evicted glyphs, so the text is
+    /// rasterized again.
+    pub fn rasterize_all(&mut self, atlas: &mut TextureAtlas, descs: &[Text], sprites: &mut Vec<Sprite>) {
+        let start = sprites.len();
+        let generation = self.generation();
+        for desc in descs {
+            self.rasterize(atlas, desc, sprites);
+        }
+        if generation != self.generation() {
+            sprites.truncate(start);
+            let generation = self.generation();
+            for desc in descs {
+                self.rasterize(atlas, desc, sprites);
+            }
+            if generation != self.generation() {
+                warn!("Text uses more distinct glyphs than fit in the glyph cache.");
+            }
+        }
+    }
+
+    /// Measures the bounds of text as it would be drawn, including its alignment. Horizontally the
+    /// bounds cover the glyphs, and vertically they cover every line, so text with the same number
+    /// of lines measures the same height.
+    pub fn measure(&mut self, desc: &Text) -> AABB2D {
+        let (offset, height) = self.layout(desc);
+        let (min_x, max_x) =
+            self.glyphs.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), glyph| {
+                (min.min(glyph.x), max.max(glyph.x + glyph.width as f32))
+            });
+        let (min_x, max_x) = if min_x > max_x {
+            (0.0, 0.0)
+        } else {
+            (min_x, max_x)
+        };
+        AABB2D::new(offset.x + min_x, offset.y - height, offset.x + max_x, offset.y)
+    }
+
     pub fn rasterize(&mut self, atlas: &mut TextureAtlas, desc: &Text, sprites: &mut Vec<Sprite>) {
-        let font_index = desc.font.key();
-        let font = &self.fonts[font_index];
-        self.layout.reset(&LayoutSettings {
-            x: desc.pos.x,
-            y: desc.pos.y,
-            max_width: desc.max_width,
-            max_height: Some(500.0),
-            horizontal_align: HorizontalAlign::Center,
-            vertical_align: VerticalAlign::Middle,
-            ..LayoutSettings::default()
-        });
-        let style = TextStyle::new(&desc.string, desc.scale as f32, font_index);
-        self.layout.append(self.fonts.as_slice(), &style);
-
-        for &position in self.layout.glyphs() {
-            if position.width == 0 {
+        let (offset, _) = self.layout(desc);
+        for &position in &self.glyphs {
+            // Characters the font doesn't support are drawn as a box when the font doesn't have a
+            // visible glyph for them.
+            let data = position.char_data;
+            let fallback = data.is_missing() && !data.is_whitespace() && !data.is_control();
+            if position.width == 0 && !fallback {
                 continue;
             }
             let value = match self.cache.get(&position.key).copied() {
                 Some(value) => value,
                 None => {
-                    let (metrics, bitmap) = font.rasterize_config(position.key);
-                    let bitmap = Self::alpha_to_rgba(&bitmap);
-                    let texture = Image::from_vec(bitmap, metrics.width as u32, metrics.height as u32);
-                    let value = CharCacheValue {
-                        uv: atlas.add(texture),
-                        size: Vector2::new(metrics.width as f32, metrics.height as f32),
+                    let (metrics, bitmap) =
+                        self.fonts[position.key.font_index].rasterize_config(position.key);
+                    let texture = if metrics.width > 0 && metrics.height > 0 {
+                        Image::from_vec(
+                            Self::alpha_to_rgba(&bitmap),
+                            metrics.width as u32,
+                            metrics.height as u32,
+                        )
+                    } else {
+                        Self::tofu(position.key.px)
+                    };
+                    let generation = self.pages.generation;
+                    let uv = self.pages.add(atlas, &texture);
+                    if generation != self.pages.generation {
+                        self.cache.clear();
+                    }
+                    let value = match uv {
+                        Some(uv) => CharCacheValue {
+                            uv,
+                            size: Vector2::new(texture.width() as f32, texture.height() as f32),
+                        },
+                        None => continue,
                     };
                     self.cache.insert(position.key, value);
                     value
                 }
             };
             sprites.push(Sprite::new(
-                Vector3::new(position.x, position.y, desc.pos.z),
+                Vector3::new(position.x + offset.x, position.y + offset.y, desc.pos.z),
                 value.size,
                 Texture(value.uv),
                 desc.color,
@@ -94,6 +215,90 @@ impl TextCache {
         }
     }
 
+    /// Lays out the text's glyphs, returning the offset they're moved by and the height of the
+    /// text.
+    fn layout(&mut self, desc: &Text) -> (Vector2<f32>, f32) {
+        let font_index = desc.font.key();
+        let font = &self.fonts[font_index];
+        let scale = desc.scale as f32;
+        let horizontal = match desc.horizontal_align {
+            HorizontalAlign::Left => 0.0,
+            HorizontalAlign::Center => 0.5,
+            HorizontalAlign::Right => 1.0,
+        };
+
+        // Glyphs are laid out with the top left of the text at the origin, then moved into place.
+        self.glyphs.clear();
+        let height = match desc.max_width {
+            Some(max_width) => {
+                self.layout.reset(&LayoutSettings {
+                    x: (-max_width * horizontal).floor(),
+                    max_width: Some(max_width),
+                    horizontal_align: match desc.horizontal_align {
+                        HorizontalAlign::Left => fontdue::layout::HorizontalAlign::Left,
+                        HorizontalAlign::Center => fontdue::layout::HorizontalAlign::Center,
+                        HorizontalAlign::Right => fontdue::layout::HorizontalAlign::Right,
+                    },
+                    ..LayoutSettings::default()
+                });
+                self.layout.append(self.fonts.as_slice(), &TextStyle::new(&desc.string, scale, font_index));
+                self.glyphs.extend_from_slice(self.layout.glyphs());
+                self.layout.height()
+            }
+            None => {
+                // Without a max width lines only break on new lines, so each line is laid out and
+                // aligned on its own.
+                let mut height = 0.0;
+                for line in desc.string.split('\n') {
+                    self.layout.reset(&LayoutSettings {
+                        y: -height,
+                        ..LayoutSettings::default()
+                    });
+                    self.layout.append(self.fonts.as_slice(), &TextStyle::new(line, scale, font_index));
+                    height += self.layout.height();
+                    let glyphs = self.layout.glyphs();
+                    let width =
+                        glyphs.iter().fold(0.0f32, |width, glyph| width.max(glyph.x + glyph.width as f32));
+                    let offset = (-width * horizontal).floor();
+                    for glyph in glyphs {
+                        let mut glyph = *glyph;
+                        glyph.x += offset;
+                        self.glyphs.push(glyph);
+                    }
+                }
+                height
+            }
+        };
+        let ascent = font.horizontal_line_metrics(scale).map_or(0.0, |metrics| metrics.ascent.ceil());
+        let offset = Vector2::new(
+            desc.pos.x,
+            desc.pos.y
+                + match desc.vertical_align {
+                    VerticalAlign::Top => 0.0,
+                    VerticalAlign::Middle => (height / 2.0).floor(),
+                    VerticalAlign::Baseline => ascent,
+                    VerticalAlign::Bottom => height,
+                },
+        );
+        (offset, height)
+    }
+
+    /// Creates the box drawn in place of unsupported characters.
+    fn tofu(px: f32) -> Image {
+        let width = (px * 0.5).ceil().max(3.0) as u32;
+        let height = (px * 0.7).ceil().max(3.0) as u32;
+        let border = (px / 24.0).ceil().max(1.0) as u32;
+        let mut image = Image::from_color(RGBA8::new_raw(0, 0, 0, 0), width, height);
+        for y in 0..height {
+            for x in 0..width {
+                if x < border || y < border || x >= width - border || y >= height - border {
+                    image.set(x, y, colors::WHITE);
+                }
+            }
+        }
+        image
+    }
+
     fn alpha_to_rgba(bitmap: &Vec<u8>) -> Vec<RGBA8> {
         let mut output = Vec::with_capacity(bitmap.len());
         for v in bitmap {
@@ -103,3 +308,135 @@ impl TextCache {
         output
     }
 }
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eviction() {
+        let mut atlas = TextureAtlas::new();
+        let mut pages = GlyphPages::new(64, 2);
+        let glyph = Image::from_color(colors::WHITE, 16, 16);
+
+        // Two pages hold 32 glyphs, with a page reserved as the first fills up.
+        let mut uvs = Vec::new();
+        for _ in 0..32 {
+            uvs.push(pages.add(&mut atlas, &glyph).unwrap());
+        }
+        assert_eq!(pages.pages.len(), 2);
+        assert_eq!(pages.generation, 0);
+        for (index, uv) in uvs.iter().enumerate() {
+            assert!(!uvs[index + 1..].contains(uv));
+        }
+
+        // Once every page is full, they're all emptied and the generation increases.
+        let uv = pages.add(&mut atlas, &glyph).unwrap();
+        assert_eq!(pages.pages.len(), 2);
+        assert_eq!(pages.generation, 1);
+        assert_eq!(uv, uvs[0]);
+
+        // Glyphs larger than a page are rejected without evicting anything.
+        assert_eq!(pages.add(&mut atlas, &Image::from_color(colors::WHITE, 65, 1)), None);
+        assert_eq!(pages.generation, 1);
+    }
+
+    #[test]
+    fn full_atlas() {
+        let mut atlas = TextureAtlas::new();
+        while atlas.reserve(1024, 1024).is_some() {}
+        let mut pages = GlyphPages::new(1024, 2);
+
+        // Without room for a page the glyph is skipped instead of panicking.
+        assert_eq!(pages.add(&mut atlas, &Image::from_color(colors::WHITE, 16, 16)), None);
+        assert!(pages.pages.is_empty());
+        assert_eq!(pages.generation, 0);
+    }
+
+    // fontdue 0.5 builds a NonZeroU16 from zero while loading a font, which aborts under debug
+    // assertions. Run with `cargo test --release -- --ignored` until fontdue is upgraded.
+    #[test]
+    #[ignore]
+    fn center() {
+        let mut cache = TextCache::new();
+        let mut text = Text {
+            string: String::from("Wide line\ni"),
+            ..Text::default()
+        };
+        cache.layout(&text);
+        let left = cache.glyphs.clone();
+        text.horizontal_align = HorizontalAlign::Center;
+        cache.layout(&text);
+        let center = cache.glyphs.clone();
+        assert_eq!(left.len(), 10);
+
+        // Each line is moved left by half of its own width, so the narrow line moves less.
+        let mut offsets = Vec::new();
+        for line in [0..9, 9..10].iter().cloned() {
+            let width = left[line.clone()]
+                .iter()
+                .fold(0.0f32, |width, glyph| width.max(glyph.x + glyph.width as f32));
+            let offset = (-width * 0.5).floor();
+            for index in line {
+                assert_eq!(center[index].x, left[index].x + offset);
+                assert_eq!(center[index].y, left[index].y);
+            }
+            offsets.push(offset);
+        }
+        assert!(offsets[0] < offsets[1] && offsets[1] < 0.0);
+    }
+
+    // fontdue 0.5 builds a NonZeroU16 from zero while loading a font, which aborts under debug
+    // assertions. Run with `cargo test --release -- --ignored` until fontdue is upgraded.
+    #[test]
+    #[ignore]
+    fn measure() {
+        let mut cache = TextCache::new();
+        let mut text = Text {
+            string: String::from("Hello"),
+            pos: Vector3::new(10.0, 20.0, 0.0),
+            ..Text::default()
+        };
+
+        // The box spans the glyphs horizontally and the line vertically.
+        let single = cache.measure(&text);
+        let (offset, height) = cache.layout(&text);
+        let min_x = cache.glyphs.iter().map(|glyph| offset.x + glyph.x).fold(f32::INFINITY, f32::min);
+        let max_x = cache
+            .glyphs
+            .iter()
+            .map(|glyph| offset.x + glyph.x + glyph.width as f32)
+            .fold(f32::NEG_INFINITY, f32::max);
+        assert_eq!(single.min.x, min_x);
+        assert_eq!(single.max.x, max_x);
+        assert_eq!(single.max.y, offset.y);
+        assert_eq!(single.max.y - single.min.y, height);
+        assert!(height > 0.0);
+
+        // Another line of the same text adds its height without changing the width.
+        text.string = String::from("Hello\nHello");
+        let multi = cache.measure(&text);
+        assert_eq!((multi.min.x, multi.max.x), (single.min.x, single.max.x));
+        assert_eq!(multi.max.y, single.max.y);
+        assert_eq!(multi.max.y - multi.min.y, height * 2.0);
+
+        // Empty text has no width, but still covers its line.
+        text.string = String::new();
+        let empty = cache.measure(&text);
+        assert_eq!(empty.min.x, empty.max.x);
+        assert_eq!(empty.max.y - empty.min.y, height);
+    }
+
+    #[test]
+    fn tofu() {
+        let image = TextCache::tofu(24.0);
+        assert_eq!((image.width(), image.height()), (12, 17));
+        assert_eq!(image.get(0, 0), colors::WHITE);
+        assert_eq!(image.get(11, 16), colors::WHITE);
+        assert_eq!(image.get(6, 8).a, 0);
+    }
+}
@@ -1,12 +1,13 @@
 use crate::colors::*;
-use crate::texture::packer::Packer;
+use crate::texture::packer::{Packer, Rect};
 use crate::texture::*;
+use crate::types::TextureError;
 use cgmath::*;
 
 const MAX: u32 = 65536;
 const SIZE: u32 = 4096;
 pub const PIXEL_SIZE: u32 = MAX / SIZE;
-const NUDGE: u16 = 4;
+const NUDGE: u32 = 4;
 
 pub struct TextureAtlas {
     packer: Packer,
@@ -21,25 +22,39 @@ impl TextureAtlas {
             atlas: Image::from_color(WHITE, SIZE, SIZE),
             dirty: false,
         };
-        atlas.add(Image::from_color(WHITE, 1, 1));
+        atlas.add(Image::from_color(WHITE, 1, 1)).expect("Unable to fit the default texture into the atlas.");
         atlas
     }
 
-    pub fn add(&mut self, texture: Image) -> Vector4<u16> {
-        if let Some(rect) = self.packer.pack(texture.width(), texture.height()) {
-            self.atlas.set_texture(rect.x, rect.y, &texture);
-            self.dirty = true;
-            Vector4::new(
-                (rect.x * PIXEL_SIZE) as u16 + NUDGE,            // Left
-                ((rect.x + rect.w) * PIXEL_SIZE) as u16 - NUDGE, // Right
-                (rect.y * PIXEL_SIZE) as u16 + NUDGE,            // Top
-                ((rect.y + rect.h) * PIXEL_SIZE) as u16 - NUDGE, // Bottom
-            )
-        } else {
-            panic!("Unable to fit texture into atlas.");
+    /// Adds the texture to the atlas, returning its uv. Returns an error if there isn't room left
+    /// in the atlas for it.
+    pub fn add(&mut self, texture: Image) -> Result<Vector4<u16>, TextureError> {
+        match self.packer.pack(texture.width(), texture.height()) {
+            Some(rect) => Ok(self.set(rect.x, rect.y, &texture)),
+            None => Err(TextureError::AtlasFull),
         }
     }
 
+    /// Reserves an empty region of the atlas, returning its bounds in pixels. The region can be
+    /// filled later with `set`.
+    pub fn reserve(&mut self, width: u32, height: u32) -> Option<Rect> {
+        self.packer.pack(width, height)
+    }
+
+    /// Writes the texture into the atlas with its top left corner at the given pixel.
+    pub fn set(&mut self, x: u32, y: u32, texture: &Image) -> Vector4<u16> {
+        self.atlas.set_texture(x, y, texture);
+        self.dirty = true;
+        // The far edges are nudged in before narrowing, since an edge at the end of the atlas is
+        // one past the largest u16.
+        Vector4::new(
+            (x * PIXEL_SIZE + NUDGE) as u16,                      // Left
+            ((x + texture.width()) * PIXEL_SIZE - NUDGE) as u16,  // Right
+            (y * PIXEL_SIZE + NUDGE) as u16,                      // Top
+            ((y + texture.height()) * PIXEL_SIZE - NUDGE) as u16, // Bottom
+        )
+    }
+
     pub fn sync(&mut self) -> Option<&Image> {
         if self.dirty {
             self.dirty = false;
@@ -49,3 +64,26 @@ impl TextureAtlas {
         }
     }
 }
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full() {
+        let mut atlas = TextureAtlas::new();
+        assert_eq!(atlas.add(Image::from_color(WHITE, SIZE + 1, 1)), Err(TextureError::AtlasFull));
+        assert!(atlas.add(Image::from_color(WHITE, 64, 64)).is_ok());
+    }
+
+    #[test]
+    fn far_edge() {
+        let mut atlas = TextureAtlas::new();
+        let uv = atlas.set(SIZE - 16, SIZE - 16, &Image::from_color(WHITE, 16, 16));
+        assert_eq!(uv, Vector4::new(65284, 65532, 65284, 65532));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::texture::formats;
-use crate::{TextureFormat, RGBA8};
+use crate::{TextureError, TextureFormat, RGBA8};
 
 #[derive(Clone, Debug)]
 pub struct Image {
@@ -9,7 +9,7 @@ pub struct Image {
 }
 
 impl Image {
-    pub fn from_raw(bytes: &[u8], format: TextureFormat) -> Image {
+    pub fn from_raw(bytes: &[u8], format: TextureFormat) -> Result<Image, TextureError> {
         match format {
             TextureFormat::PNG => formats::png::read(bytes),
         }
@@ -6,3 +6,4 @@ mod packer;
 pub use self::atlas::TextureAtlas;
 pub use self::atlas::PIXEL_SIZE;
 pub use self::image::Image;
+pub use self::packer::{Packer, Rect};
@@ -136,7 +136,8 @@ impl Packer {
         };
 
         assert!(skyline.right() <= self.border.right());
-        assert!(skyline.y <= self.border.bottom());
+        // A skyline sitting on the bottom border is full, and can't be packed into.
+        assert!(skyline.y <= self.border.bottom() + 1);
 
         self.skylines.insert(index, skyline);
 
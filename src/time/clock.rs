@@ -0,0 +1,219 @@
+use crate::time::{Instant, SmoothedFps};
+use core::time::Duration;
+
+/// Measures the time between frames, and keeps statistics over a window of recent frames. Deltas
+/// are clamped to a maximum so a long stall, such as pausing in a debugger, doesn't produce one
+/// huge step, while the statistics record the real frame times. The engine keeps one up to date,
+/// see `Engine::clock`.
+#[derive(Clone, Debug)]
+pub struct Clock {
+    last: Instant,
+    max_delta: Option<f32>,
+    clamped_frames: u64,
+    frames: Vec<Duration>,
+    next: usize,
+    window: usize,
+    smoothed: SmoothedFps,
+}
+
+impl Clock {
+    /// Creates a new clock starting now. The maximum delta defaults to 0.25 seconds and the
+    /// statistics cover 60 frames.
+    pub fn new() -> Clock {
+        Clock::new_at(Instant::now())
+    }
+
+    /// Creates a new clock starting at the given time.
+    pub fn new_at(now: Instant) -> Clock {
+        Clock {
+            last: now,
+            max_delta: Some(0.25),
+            clamped_frames: 0,
+            frames: Vec::new(),
+            next: 0,
+            window: 60,
+            smoothed: SmoothedFps::default(),
+        }
+    }
+
+    /// Sets the largest delta in seconds that tick can return, or None to disable the clamp.
+    pub fn set_max_delta(&mut self, max_delta: Option<f32>) {
+        self.max_delta = max_delta;
+    }
+
+    /// Gets the largest delta in seconds that tick can return, or None if the delta isn't clamped.
+    pub fn max_delta(&self) -> Option<f32> {
+        self.max_delta
+    }
+
+    /// Gets the number of ticks that had their delta clamped by the max delta.
+    pub fn clamped_frames(&self) -> u64 {
+        self.clamped_frames
+    }
+
+    /// Sets the number of frames the statistics cover. This clears the recorded frames.
+    pub fn set_window(&mut self, frames: usize) {
+        assert!(frames > 0, "The frame window must contain at least one frame.");
+        self.window = frames;
+        self.frames.clear();
+        self.next = 0;
+    }
+
+    /// Sets how much each new frame affects the smoothed frame rate. See
+    /// `SmoothedFps::set_smoothing`.
+    pub fn set_smoothing(&mut self, smoothing: f32) {
+        self.smoothed.set_smoothing(smoothing);
+    }
+
+    /// Returns the seconds elapsed since the previous tick, or since the clock was created.
+    pub fn tick(&mut self) -> f32 {
+        self.tick_at(Instant::now())
+    }
+
+    /// Returns the seconds elapsed between the previous tick and the given time, and records the
+    /// frame.
+    pub fn tick_at(&mut self, now: Instant) -> f32 {
+        let frame = if now > self.last {
+            now - self.last
+        } else {
+            Duration::from_secs(0)
+        };
+        self.last = now;
+        if self.frames.len() < self.window {
+            self.frames.push(frame);
+        } else {
+            self.frames[self.next] = frame;
+        }
+        self.next = (self.next + 1) % self.window;
+        let delta = frame.as_secs_f32();
+        self.smoothed.push(delta);
+        match self.max_delta {
+            Some(max_delta) if delta > max_delta => {
+                self.clamped_frames += 1;
+                max_delta
+            }
+            _ => delta,
+        }
+    }
+
+    /// Moves the clock to the given time without recording a frame, so the time in between isn't
+    /// included in the next delta. This is useful for skipping time spent paused.
+    pub fn skip_to(&mut self, now: Instant) {
+        self.last = now;
+    }
+
+    /// Gets the recorded frames from oldest to newest. This is useful for drawing a frame time
+    /// graph.
+    pub fn frames(&self) -> impl Iterator<Item = Duration> + '_ {
+        let (newer, older) = if self.frames.len() < self.window {
+            (&self.frames[..], &self.frames[..0])
+        } else {
+            self.frames.split_at(self.next)
+        };
+        older.iter().chain(newer.iter()).copied()
+    }
+
+    /// Gets the average frames per second over the recorded frames. This is 0 until a frame with
+    /// a nonzero duration has been recorded.
+    pub fn fps(&self) -> f32 {
+        let average = self.average_frame_time().as_secs_f32();
+        if average > 0.0 {
+            1.0 / average
+        } else {
+            0.0
+        }
+    }
+
+    /// Gets the frame rate smoothed with an exponential moving average, so it reads steadily when
+    /// displayed.
+    pub fn smoothed_fps(&self) -> f32 {
+        self.smoothed.fps()
+    }
+
+    /// Gets the average duration of the recorded frames.
+    pub fn average_frame_time(&self) -> Duration {
+        if self.frames.is_empty() {
+            return Duration::from_secs(0);
+        }
+        self.frames.iter().sum::<Duration>() / self.frames.len() as u32
+    }
+
+    /// Gets the shortest recorded frame.
+    pub fn min_frame_time(&self) -> Duration {
+        self.frames.iter().copied().min().unwrap_or_default()
+    }
+
+    /// Gets the longest recorded frame.
+    pub fn max_frame_time(&self) -> Duration {
+        self.frames.iter().copied().max().unwrap_or_default()
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Clock {
+        Clock::new()
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock() {
+        let mut now = Instant::now();
+        let mut clock = Clock::new_at(now);
+        clock.set_window(4);
+        assert_eq!(clock.fps(), 0.0);
+
+        now += Duration::from_millis(50);
+        assert!((clock.tick_at(now) - 0.05).abs() < 1e-6);
+        assert!((clock.fps() - 20.0).abs() < 1e-3);
+
+        // Stalls are clamped, but still recorded as they happened.
+        now += Duration::from_secs(5);
+        assert_eq!(clock.tick_at(now), 0.25);
+        assert_eq!(clock.max_frame_time(), Duration::from_secs(5));
+        assert_eq!(clock.clamped_frames(), 1);
+
+        // Skipped time isn't included in the next delta.
+        now += Duration::from_secs(1);
+        clock.skip_to(now);
+        now += Duration::from_millis(10);
+        assert!((clock.tick_at(now) - 0.01).abs() < 1e-6);
+
+        // Once the window is filled with steady frames, the average converges.
+        for _ in 0..4 {
+            now += Duration::from_millis(10);
+            clock.tick_at(now);
+        }
+        assert!((clock.fps() - 100.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn frames() {
+        let ms = Duration::from_millis;
+        let mut now = Instant::now();
+        let mut clock = Clock::new_at(now);
+        clock.set_window(3);
+        for frame in [10, 30].iter() {
+            now += ms(*frame);
+            clock.tick_at(now);
+        }
+        assert_eq!(clock.frames().collect::<Vec<_>>(), vec![ms(10), ms(30)]);
+        assert_eq!(clock.average_frame_time(), ms(20));
+
+        // The oldest frame is replaced once full.
+        for frame in [20, 50].iter() {
+            now += ms(*frame);
+            clock.tick_at(now);
+        }
+        assert_eq!(clock.frames().collect::<Vec<_>>(), vec![ms(30), ms(20), ms(50)]);
+        assert_eq!(clock.min_frame_time(), ms(20));
+        assert_eq!(clock.max_frame_time(), ms(50));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::time::Instant;
+
+/// Drives a fixed rate update, such as physics, from a variable frame rate. Each frame, `tick`
+/// reports how many fixed steps to run to catch up to the current time. Leftover time that isn't
+/// enough for a full step is carried into the next frame.
+#[derive(Copy, Clone, Debug)]
+pub struct FixedTimestep {
+    step: f64,
+    accumulator: f64,
+    max_steps: u32,
+    max_delta: Option<f32>,
+    last: Option<Instant>,
+}
+
+impl FixedTimestep {
+    /// Creates a new timestep that runs the given number of steps per second. The maximum number of
+    /// steps per tick defaults to 8, and the elapsed time isn't clamped.
+    pub fn new(hz: f64) -> FixedTimestep {
+        assert!(hz > 0.0, "The timestep rate must be positive.");
+        FixedTimestep {
+            step: 1.0 / hz,
+            accumulator: 0.0,
+            max_steps: 8,
+            max_delta: None,
+            last: None,
+        }
+    }
+
+    /// Sets the maximum number of steps a single tick can return. If the app stalls for longer than
+    /// this many steps, the extra time is dropped instead of being caught up, so a slow step can't
+    /// cause every following frame to fall further behind.
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.max_steps = max_steps;
+    }
+
+    /// Sets the most time in seconds a single tick can add, or None to disable the clamp. Time
+    /// beyond this is dropped before it's accumulated, matching the clamp on `Clock::tick`.
+    pub fn set_max_delta(&mut self, max_delta: Option<f32>) {
+        self.max_delta = max_delta;
+    }
+
+    /// The length of a step in seconds.
+    pub fn step(&self) -> f32 {
+        self.step as f32
+    }
+
+    /// Advances to the given time, returning the number of fixed steps to run. The first tick only
+    /// records the time and returns 0.
+    pub fn tick(&mut self, now: Instant) -> u32 {
+        let last = match self.last.replace(now) {
+            Some(last) => last,
+            None => return 0,
+        };
+        if now > last {
+            let elapsed = (now - last).as_secs_f64();
+            self.accumulator += match self.max_delta {
+                Some(max_delta) => elapsed.min(max_delta as f64),
+                None => elapsed,
+            };
+        }
+        let steps = (self.accumulator / self.step) as u64;
+        if steps > self.max_steps as u64 {
+            self.accumulator %= self.step;
+            self.max_steps
+        } else {
+            self.accumulator -= steps as f64 * self.step;
+            steps as u32
+        }
+    }
+
+    /// How far the current time is between the last step and the next, from 0 to 1. Rendering can
+    /// use this to interpolate between the previous and current fixed states.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.step).clamp(0.0, 1.0) as f32
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[test]
+    fn steps() {
+        let mut timestep = FixedTimestep::new(60.0);
+        let mut now = Instant::now();
+        assert_eq!(timestep.tick(now), 0);
+
+        // Irregular frame times that add up to 10 seconds.
+        let frames = [3, 17, 40, 1, 16, 23];
+        let mut elapsed = 0;
+        let mut steps = 0;
+        for &frame in frames.iter().cycle() {
+            let frame = frame.min(10_000 - elapsed);
+            elapsed += frame;
+            now += Duration::from_millis(frame);
+            steps += timestep.tick(now);
+            if elapsed == 10_000 {
+                break;
+            }
+        }
+        now += Duration::from_millis(1);
+        steps += timestep.tick(now);
+        assert_eq!(steps, 600);
+        assert!(timestep.alpha() < 0.1);
+    }
+
+    #[test]
+    fn max_steps() {
+        let mut timestep = FixedTimestep::new(10.0);
+        timestep.set_max_steps(4);
+        let mut now = Instant::now();
+        timestep.tick(now);
+        now += Duration::from_millis(1050);
+        assert_eq!(timestep.tick(now), 4);
+        assert!((timestep.alpha() - 0.5).abs() < 0.01);
+        now += Duration::from_millis(100);
+        assert_eq!(timestep.tick(now), 1);
+    }
+
+    #[test]
+    fn max_delta() {
+        let mut timestep = FixedTimestep::new(10.0);
+        timestep.set_max_delta(Some(0.25));
+        let mut now = Instant::now();
+        timestep.tick(now);
+
+        // Only the first quarter second of the stall is caught up.
+        now += Duration::from_millis(700);
+        assert_eq!(timestep.tick(now), 2);
+        assert!((timestep.alpha() - 0.5).abs() < 0.01);
+        now += Duration::from_millis(60);
+        assert_eq!(timestep.tick(now), 1);
+    }
+}
@@ -0,0 +1,78 @@
+/// Tracks a frame rate smoothed with an exponential moving average over frame times, so it reads
+/// steadily instead of jumping around from frame to frame.
+#[derive(Copy, Clone, Debug)]
+pub struct SmoothedFps {
+    smoothing: f32,
+    frame_time: f32,
+}
+
+impl SmoothedFps {
+    /// Creates a new tracker. See `set_smoothing` for the meaning of the smoothing factor.
+    pub fn new(smoothing: f32) -> SmoothedFps {
+        let mut fps = SmoothedFps {
+            smoothing: 0.0,
+            frame_time: 0.0,
+        };
+        fps.set_smoothing(smoothing);
+        fps
+    }
+
+    /// Sets how much each new frame affects the average, from 0 to 1. Smaller values are steadier
+    /// but slower to react to changes. A value of 1 disables smoothing.
+    pub fn set_smoothing(&mut self, smoothing: f32) {
+        self.smoothing = smoothing.clamp(0.0, 1.0);
+    }
+
+    /// Records the time of a frame in seconds.
+    pub fn push(&mut self, delta: f32) {
+        if delta <= 0.0 {
+            return;
+        }
+        if self.frame_time == 0.0 {
+            self.frame_time = delta;
+        } else {
+            self.frame_time += (delta - self.frame_time) * self.smoothing;
+        }
+    }
+
+    /// Gets the smoothed frames per second. This is 0 until a frame has been recorded.
+    pub fn fps(&self) -> f32 {
+        if self.frame_time == 0.0 {
+            0.0
+        } else {
+            1.0 / self.frame_time
+        }
+    }
+}
+
+impl Default for SmoothedFps {
+    fn default() -> SmoothedFps {
+        SmoothedFps::new(0.1)
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoothing() {
+        let mut fps = SmoothedFps::new(0.5);
+        assert_eq!(fps.fps(), 0.0);
+        fps.push(0.01);
+        assert_eq!(fps.fps(), 100.0);
+        fps.push(0.03);
+        assert_eq!(fps.fps(), 50.0);
+        fps.push(0.0);
+        assert_eq!(fps.fps(), 50.0);
+
+        let mut fps = SmoothedFps::new(1.0);
+        fps.push(0.01);
+        fps.push(0.02);
+        assert_eq!(fps.fps(), 50.0);
+    }
+}
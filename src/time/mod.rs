@@ -1,6 +1,14 @@
+mod clock;
 mod convert;
+mod fixed;
+mod fps;
+mod stopwatch;
 mod timer;
 
+pub use self::clock::*;
 pub use self::convert::*;
+pub use self::fixed::*;
+pub use self::fps::*;
+pub use self::stopwatch::*;
 pub use self::timer::*;
 pub use instant::Instant;
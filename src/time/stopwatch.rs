@@ -0,0 +1,122 @@
+/// Measures elapsed game time while running. Stopwatches are advanced by the update event's delta
+/// rather than reading the system clock, so they only count time the game actually updated for,
+/// and stop counting while updates are paused.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Stopwatch {
+    elapsed: f32,
+    running: bool,
+}
+
+impl Stopwatch {
+    /// Creates a new stopped stopwatch with no elapsed time.
+    pub fn new() -> Stopwatch {
+        Stopwatch::default()
+    }
+
+    /// Starts counting time.
+    pub fn start(&mut self) {
+        self.running = true;
+    }
+
+    /// Stops counting time, keeping the time elapsed so far.
+    pub fn stop(&mut self) {
+        self.running = false;
+    }
+
+    /// Sets the elapsed time back to zero. This doesn't change whether the stopwatch is running.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// If the stopwatch is counting time.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Gets the time counted in seconds.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Advances the stopwatch by the delta in seconds if it's running.
+    pub fn update(&mut self, delta: f32) {
+        if self.running {
+            self.elapsed += delta;
+        }
+    }
+}
+
+/// Fires repeatedly at a fixed interval of game time. Like the stopwatch, it's advanced by the
+/// update event's delta. Time past each firing is carried over, so the timer doesn't drift no
+/// matter how the interval lines up with frames.
+#[derive(Copy, Clone, Debug)]
+pub struct RepeatTimer {
+    interval: f32,
+    elapsed: f32,
+}
+
+impl RepeatTimer {
+    /// Creates a new timer that fires every interval, in seconds.
+    pub fn new(interval: f32) -> RepeatTimer {
+        assert!(interval > 0.0, "The repeat interval must be positive.");
+        RepeatTimer {
+            interval,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Gets the interval in seconds.
+    pub fn interval(&self) -> f32 {
+        self.interval
+    }
+
+    /// Sets the time since the timer last fired back to zero.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// Advances the timer by the delta in seconds, returning the number of times it fired.
+    pub fn update(&mut self, delta: f32) -> u32 {
+        self.elapsed += delta;
+        let count = (self.elapsed / self.interval) as u32;
+        self.elapsed -= count as f32 * self.interval;
+        count
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stopwatch() {
+        let mut stopwatch = Stopwatch::new();
+        stopwatch.update(1.0);
+        assert_eq!(stopwatch.elapsed(), 0.0);
+        stopwatch.start();
+        stopwatch.update(0.5);
+        stopwatch.update(0.25);
+        stopwatch.stop();
+        stopwatch.update(1.0);
+        assert_eq!(stopwatch.elapsed(), 0.75);
+        stopwatch.reset();
+        assert_eq!(stopwatch.elapsed(), 0.0);
+    }
+
+    #[test]
+    fn repeat() {
+        let mut timer = RepeatTimer::new(0.5);
+        assert_eq!(timer.update(0.25), 0);
+        assert_eq!(timer.update(0.5), 1);
+        assert_eq!(timer.update(0.75), 2);
+
+        // Many small deltas fire the expected number of times without drifting.
+        let mut timer = RepeatTimer::new(0.5);
+        let count: u32 = (0..1000).map(|_| timer.update(0.0625)).sum();
+        assert_eq!(count, 125);
+    }
+}
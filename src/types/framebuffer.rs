@@ -0,0 +1,50 @@
+use core::fmt;
+
+/// Error returned when a framebuffer isn't complete, so drawing to it would produce garbage. This
+/// holds the status reported by glCheckFramebufferStatus.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FramebufferError {
+    status: u32,
+}
+
+impl FramebufferError {
+    pub(crate) fn new(status: u32) -> FramebufferError {
+        FramebufferError {
+            status,
+        }
+    }
+
+    /// Gets the status reported by glCheckFramebufferStatus.
+    pub fn status(&self) -> u32 {
+        self.status
+    }
+}
+
+impl fmt::Display for FramebufferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.status {
+            glow::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => "incomplete attachment",
+            glow::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => "missing attachment",
+            glow::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => "incomplete multisample",
+            glow::FRAMEBUFFER_UNSUPPORTED => "unsupported",
+            _ => "unknown",
+        };
+        write!(f, "Framebuffer is incomplete: {} (0x{:04X})", reason, self.status)
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = FramebufferError::new(glow::FRAMEBUFFER_UNSUPPORTED);
+        assert_eq!(error.status(), 0x8CDD);
+        assert_eq!(error.to_string(), "Framebuffer is incomplete: unsupported (0x8CDD)");
+    }
+}
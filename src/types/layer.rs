@@ -32,4 +32,70 @@ impl LayerTransform {
             * Matrix4::from_translation(translation.extend(0.0))
             * Matrix4::from_angle_z(Rad(core::f32::consts::PI * 2.0 * self.rotation))
     }
+
+    /// Converts a position in the layer's coordinates into a position on screen, in the engine's
+    /// screen coordinates. This is the inverse of `screen_to_world`.
+    pub fn world_to_screen(&self, world: Vector2<f32>) -> Vector2<f32> {
+        (self.to_matrix() * world.extend(0.0).extend(1.0)).truncate().truncate()
+    }
+
+    /// Converts a position on screen into the layer's coordinates. This is the exact inverse of the
+    /// transform used to draw the layer, so a cursor position maps onto the sprites drawn under
+    /// it. Screen positions are in the engine's screen coordinates, measured in pixels from the
+    /// center of the window with Y pointing up, which is how cursor positions are reported with
+    /// CursorSpace::Pixels.
+    pub fn screen_to_world(&self, screen: Vector2<f32>) -> Vector2<f32> {
+        match self.to_matrix().invert() {
+            Some(inverse) => (inverse * screen.extend(0.0).extend(1.0)).truncate().truncate(),
+            None => screen,
+        }
+    }
+}
+
+/// How a layer's sprites are combined with what's already been drawn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Sprites are drawn over what's behind them by their alpha. This is the default.
+    Alpha,
+    /// Sprites add their color, scaled by their alpha, to what's behind them, so overlapping
+    /// sprites get brighter. This is useful for particles and glows.
+    Additive,
+    /// Sprites multiply what's behind them by their color, ignoring their alpha, so white leaves it
+    /// unchanged and darker colors darken it. This is useful for light maps and shadows.
+    Multiply,
+    /// Sprites replace what's behind them, ignoring their alpha.
+    None,
+}
+
+// Deriving Default on enums needs a newer compiler than the engine supports.
+#[allow(clippy::derivable_impls)]
+impl Default for BlendMode {
+    fn default() -> BlendMode {
+        BlendMode::Alpha
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_to_world() {
+        let transform = LayerTransform {
+            translation: Vector2::new(-30.0, 12.0),
+            scale: 2.0,
+            rotation: 0.25,
+        };
+        let world = Vector2::new(5.0, -7.0);
+        let screen = transform.world_to_screen(world);
+        assert!((screen - Vector2::new(-46.0, 34.0)).magnitude() < 1e-4);
+        assert!((transform.screen_to_world(screen) - world).magnitude() < 1e-4);
+
+        // With the default transform, screen and world coordinates are the same.
+        assert_eq!(LayerTransform::new().screen_to_world(Vector2::new(3.0, 4.0)), Vector2::new(3.0, 4.0));
+    }
 }
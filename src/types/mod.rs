@@ -1,12 +1,18 @@
 mod color;
+mod framebuffer;
 mod layer;
+mod monitor;
+mod shader;
 mod sprite;
 mod text;
 mod texture;
 mod window;
 
 pub use color::*;
+pub use framebuffer::*;
 pub use layer::*;
+pub use monitor::*;
+pub use shader::*;
 pub use sprite::*;
 pub use text::*;
 pub use texture::*;
@@ -0,0 +1,32 @@
+use cgmath::*;
+
+/// Information describing a monitor attached to the system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorInfo {
+    /// The human readable name of the monitor. Combined with the position, this is stable across
+    /// launches and can be used to persist a chosen monitor. This is empty if the platform doesn't
+    /// report a name.
+    pub name: String,
+    /// The position of the top left corner of the monitor on the desktop. Units are measured in
+    /// physical pixels.
+    pub position: Vector2<i32>,
+    /// The resolution of the monitor. Units are measured in physical pixels.
+    pub size: Vector2<u32>,
+    /// The refresh rate of the monitor in hertz. This is 0 if the platform doesn't report one.
+    pub refresh_rate: u16,
+    /// The ratio between physical pixels and logical pixels on this monitor.
+    pub scale_factor: f32,
+}
+
+/// A video mode supported by a monitor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VideoMode {
+    /// The name of the monitor this mode belongs to. This matches the name in MonitorInfo.
+    pub monitor: String,
+    /// The resolution of the mode. Units are measured in physical pixels.
+    pub size: Vector2<u32>,
+    /// The number of bits per pixel of the mode.
+    pub bit_depth: u16,
+    /// The refresh rate of the mode in hertz.
+    pub refresh_rate: u16,
+}
@@ -0,0 +1,40 @@
+use core::fmt;
+
+/// Errors that can occur while building a shader program. Each carries the info log reported by
+/// the graphics driver.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShaderError {
+    /// The vertex shader failed to compile.
+    VertexCompile(String),
+    /// The fragment shader failed to compile.
+    FragmentCompile(String),
+    /// The compiled shaders failed to link into a program.
+    Link(String),
+}
+
+impl fmt::Display for ShaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShaderError::VertexCompile(log) => write!(f, "Unable to compile vertex shader: {}", log),
+            ShaderError::FragmentCompile(log) => write!(f, "Unable to compile fragment shader: {}", log),
+            ShaderError::Link(log) => write!(f, "Unable to link shader program: {}", log),
+        }
+    }
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let log = String::from("0:3(1): error: syntax error, unexpected IDENTIFIER");
+        let error = ShaderError::FragmentCompile(log.clone());
+        assert_eq!(error.to_string(), format!("Unable to compile fragment shader: {}", log));
+        assert!(ShaderError::Link(log.clone()).to_string().ends_with(&log));
+    }
+}
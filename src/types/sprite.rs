@@ -8,6 +8,14 @@ use cgmath::*;
 pub struct Sprite {
     /// Position of the sprite. The X and Y coordinates represent the bottom left corner of the
     /// sprite. The Z coordinate represents sprite depth. Units are measured in pixels.
+    ///
+    /// Depth ranges from -1 to 1, and sprites with a larger depth are drawn on top of sprites with
+    /// a smaller depth, regardless of which layer they're in or the order they're drawn. When two
+    /// sprites have the same depth, the one drawn first stays on top, both within a layer and
+    /// across layers, until the depth buffer is cleared. To draw a layer such as a HUD over
+    /// everything before it, give it a larger depth or clear with ClearMode::DEPTH before drawing
+    /// it. Fully transparent pixels don't affect depth, but partially transparent pixels do, so
+    /// translucent sprites should be drawn after the sprites they cover.
     pub pos: Vector3<f32>,
     /// Units are measured in pixels.
     pub size: Vector2<u16>,
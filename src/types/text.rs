@@ -34,7 +34,7 @@ impl Default for FontToken {
 pub struct Text {
     /// Text that's being drawn.
     pub string: String,
-    /// Position of the text. The X and Y coordinates represent the bottom left corner of the text.
+    /// Position of the text. The X and Y coordinates represent the point the text is aligned to.
     /// The Z coordinate represents sprite depth. Units are measured in pixels.
     pub pos: Vector3<f32>,
     /// Max width of the text before it's pushed to a new line. If this is set to None, it will
@@ -46,6 +46,10 @@ pub struct Text {
     pub color: RGBA8,
     /// Font to use for the text. This is the engine font by default.
     pub font: FontToken,
+    /// Horizontal alignment of the text relative to the position. The default is Left.
+    pub horizontal_align: HorizontalAlign,
+    /// Vertical alignment of the text relative to the position. The default is Top.
+    pub vertical_align: VerticalAlign,
 }
 
 impl Default for Text {
@@ -57,6 +61,8 @@ impl Default for Text {
             scale: 24,
             color: BLACK,
             font: FontToken::default(),
+            horizontal_align: HorizontalAlign::Left,
+            vertical_align: VerticalAlign::Top,
         }
     }
 }
@@ -77,6 +83,7 @@ impl Text {
             scale,
             color,
             font,
+            ..Text::default()
         }
     }
 
@@ -85,3 +92,29 @@ impl Text {
         self.string.push_str(&string);
     }
 }
+
+/// Horizontal alignment of text relative to its position. Each line is aligned independently. If
+/// the text has a max width, lines are aligned within a box of that width, and the box is aligned
+/// to the position.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HorizontalAlign {
+    /// The left edge of the text is at the position.
+    Left,
+    /// The text is centered on the position.
+    Center,
+    /// The right edge of the text is at the position.
+    Right,
+}
+
+/// Vertical alignment of text relative to its position.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum VerticalAlign {
+    /// The top of the first line is at the position.
+    Top,
+    /// The text is centered on the position.
+    Middle,
+    /// The baseline of the first line is at the position.
+    Baseline,
+    /// The bottom of the last line is at the position.
+    Bottom,
+}
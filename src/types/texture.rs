@@ -1,5 +1,6 @@
 use crate::texture::PIXEL_SIZE;
 use cgmath::*;
+use core::fmt;
 
 /// Enumeration for all the loadable texture formats. Currently only PNG is supported.
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -7,6 +8,47 @@ pub enum TextureFormat {
     PNG,
 }
 
+impl TextureFormat {
+    /// Detects the format of an encoded texture from its header. Returns None if the format isn't
+    /// recognized.
+    pub fn detect(bytes: &[u8]) -> Option<TextureFormat> {
+        const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        if bytes.starts_with(&PNG_SIGNATURE) {
+            Some(TextureFormat::PNG)
+        } else {
+            None
+        }
+    }
+}
+
+/// Errors that can occur while loading a texture.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TextureError {
+    /// The bytes aren't in a recognized texture format.
+    UnsupportedFormat,
+    /// The texture is in a recognized format, but uses features that aren't supported, such as
+    /// indexed color.
+    UnsupportedFeature(&'static str),
+    /// The texture is malformed and couldn't be decoded.
+    InvalidData(&'static str),
+    /// There isn't room left in the texture atlas for the texture. Every texture shares the same
+    /// 4096 by 4096 atlas, so this also happens for textures larger than the atlas.
+    AtlasFull,
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TextureError::UnsupportedFormat => write!(f, "Unsupported texture format."),
+            TextureError::UnsupportedFeature(feature) => {
+                write!(f, "Unsupported texture feature: {}", feature)
+            }
+            TextureError::InvalidData(reason) => write!(f, "Invalid texture data: {}", reason),
+            TextureError::AtlasFull => write!(f, "Unable to fit texture into atlas."),
+        }
+    }
+}
+
 /// Token to reference a texture with. Has basic configuration settings.
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(transparent)]
@@ -67,3 +109,21 @@ impl Texture {
         Ok(Texture(subset))
     }
 }
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect() {
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        assert_eq!(TextureFormat::detect(&png), Some(TextureFormat::PNG));
+        assert_eq!(TextureFormat::detect(&png[..4]), None);
+        assert_eq!(TextureFormat::detect(b"GIF89a"), None);
+        assert_eq!(TextureFormat::detect(&[]), None);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::types::VideoMode;
+use core::fmt;
+
 /// Configuration settings for the window.
 #[derive(Debug, Clone, PartialEq)]
 pub struct WindowSettings {
@@ -7,6 +10,8 @@ pub struct WindowSettings {
     pub display_mode: DisplayMode,
     /// Vsync mode for the window.
     pub vsync: Vsync,
+    /// Attributes requested for the graphics context.
+    pub context: ContextSettings,
 }
 
 impl Default for WindowSettings {
@@ -19,15 +24,84 @@ impl Default for WindowSettings {
                 resizable: true,
             },
             vsync: Vsync::Disabled,
+            context: ContextSettings::default(),
         }
     }
 }
 
+/// Attributes requested for the graphics context. These only apply to native targets, the web
+/// always uses WebGL 2.
 #[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContextSettings {
+    /// The OpenGL version to request as (major, minor). If this is set to None, the latest
+    /// supported version is used. The default is None.
+    pub gl_version: Option<(u8, u8)>,
+    /// The OpenGL profile to request. The default is GlProfile::Any.
+    pub gl_profile: GlProfile,
+    /// If a debug context is requested. The default is false.
+    pub debug: bool,
+}
+
+impl Default for ContextSettings {
+    fn default() -> ContextSettings {
+        ContextSettings {
+            gl_version: None,
+            gl_profile: GlProfile::Any,
+            debug: false,
+        }
+    }
+}
+
+/// Enumeration for the OpenGL profiles that can be requested.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GlProfile {
+    /// Any profile the platform provides.
+    Any,
+    /// The core profile, without deprecated functionality.
+    Core,
+    /// The compatibility profile, with deprecated functionality.
+    Compatibility,
+}
+
+/// Information about the graphics context that was actually created, as reported by the driver.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextInfo {
+    /// The version string of the context, such as "3.3.0 Core Profile".
+    pub version: String,
+    /// The version string of the shading language.
+    pub shading_language_version: String,
+    /// The company responsible for the implementation.
+    pub vendor: String,
+    /// The name of the renderer, usually the graphics hardware.
+    pub renderer: String,
+}
+
+/// Error returned when the window or its graphics context couldn't be created, such as when the
+/// requested context attributes aren't supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextError {
+    message: String,
+}
+
+impl ContextError {
+    pub(crate) fn new(message: String) -> ContextError {
+        ContextError {
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unable to create the graphics context: {}", self.message)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum DisplayMode {
     /// Normal windowed mode.
     Windowed {
-        /// The height of the window.
+        /// The width of the window.
         width: i32,
         /// The height of the window.
         height: i32,
@@ -36,8 +110,17 @@ pub enum DisplayMode {
     },
     /// For "fake" fullscreen that takes the size of the desktop.
     WindowedFullscreen,
-    /// For "real" fullscreen with a videomode change.
-    Fullscreen,
+    /// For "real" exclusive fullscreen. On the web this is the same as WindowedFullscreen.
+    Fullscreen {
+        /// The index of the monitor in the list from `Engine::window_monitors`. If the index is out
+        /// of range, the primary monitor is used instead.
+        monitor: usize,
+        /// The video mode to switch to, usually picked from `Engine::window_video_modes`. If this
+        /// is None or the monitor doesn't support the mode, the monitor's current resolution is
+        /// used at its fastest refresh rate. If the monitor doesn't report a mode at its current
+        /// resolution either, this falls back to WindowedFullscreen.
+        video_mode: Option<VideoMode>,
+    },
 }
 
 /// Enumeration for all possible vsync settings.
@@ -48,3 +131,78 @@ pub enum Vsync {
     /// Vsync will be enabled.
     Enabled,
 }
+
+/// Enumeration for the icons the cursor can show while it's over the window.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum CursorIcon {
+    /// The platform's default cursor, usually an arrow.
+    Default,
+    /// A simple crosshair.
+    Crosshair,
+    /// A hand, often used for links.
+    Hand,
+    /// An arrow.
+    Arrow,
+    /// Indicates something is to be moved.
+    Move,
+    /// Indicates text that may be selected or edited.
+    Text,
+    /// Indicates the program is busy and can't be interacted with.
+    Wait,
+    /// Indicates help is available, often drawn as a question mark.
+    Help,
+    /// Indicates the program is busy, but can still be interacted with.
+    Progress,
+    /// Indicates something can't be done.
+    NotAllowed,
+    /// Indicates a context menu is available.
+    ContextMenu,
+    /// Indicates a cell or set of cells may be selected.
+    Cell,
+    /// Indicates vertical text that may be selected or edited.
+    VerticalText,
+    /// Indicates an alias or shortcut is to be created.
+    Alias,
+    /// Indicates something is to be copied.
+    Copy,
+    /// Indicates the dragged item can't be dropped here.
+    NoDrop,
+    /// Indicates something can be grabbed.
+    Grab,
+    /// Indicates something is grabbed.
+    Grabbing,
+    /// Indicates something can be scrolled in any direction.
+    AllScroll,
+    /// Indicates something can be zoomed in.
+    ZoomIn,
+    /// Indicates something can be zoomed out.
+    ZoomOut,
+    /// Indicates the east edge is to be moved.
+    EResize,
+    /// Indicates the north edge is to be moved.
+    NResize,
+    /// Indicates the north east corner is to be moved.
+    NeResize,
+    /// Indicates the north west corner is to be moved.
+    NwResize,
+    /// Indicates the south edge is to be moved.
+    SResize,
+    /// Indicates the south east corner is to be moved.
+    SeResize,
+    /// Indicates the south west corner is to be moved.
+    SwResize,
+    /// Indicates the west edge is to be moved.
+    WResize,
+    /// Indicates something can be resized horizontally.
+    EwResize,
+    /// Indicates something can be resized vertically.
+    NsResize,
+    /// Indicates something can be resized along the north east to south west diagonal.
+    NeswResize,
+    /// Indicates something can be resized along the north west to south east diagonal.
+    NwseResize,
+    /// Indicates a column can be resized horizontally.
+    ColResize,
+    /// Indicates a row can be resized vertically.
+    RowResize,
+}
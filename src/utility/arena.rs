@@ -0,0 +1,188 @@
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::mem;
+use core::ptr;
+use core::slice;
+use std::alloc::{alloc, dealloc, handle_alloc_error};
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+struct Chunk {
+    ptr: *mut u8,
+    layout: Layout,
+}
+
+/// A bump allocator for short lived values, such as per-frame temporaries. Allocating is a pointer
+/// bump into a chunk of memory owned by the arena, and resetting the arena makes all of its memory
+/// available again without returning it to the system. Only Copy types can be allocated, since
+/// values in the arena are never dropped.
+///
+/// Calling `reset` invalidates every reference handed out by the arena. This is enforced by the
+/// borrow checker, as `reset` takes the arena mutably while references borrow it immutably.
+pub struct Arena {
+    chunk_size: usize,
+    chunks: UnsafeCell<Vec<Chunk>>,
+    current: Cell<usize>,
+    offset: Cell<usize>,
+}
+
+impl Arena {
+    /// Creates a new arena. Memory is allocated lazily in chunks of 64 KiB.
+    pub fn new() -> Arena {
+        Arena::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a new arena that allocates memory in chunks of the given size in bytes. Allocations
+    /// larger than the chunk size get a chunk of their own.
+    pub fn with_chunk_size(chunk_size: usize) -> Arena {
+        Arena {
+            chunk_size: chunk_size.max(1),
+            chunks: UnsafeCell::new(Vec::new()),
+            current: Cell::new(0),
+            offset: Cell::new(0),
+        }
+    }
+
+    /// Moves the value into the arena, returning a reference to it.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T: Copy>(&self, value: T) -> &mut T {
+        let ptr = self.alloc_layout(Layout::new::<T>()) as *mut T;
+        unsafe {
+            ptr::write(ptr, value);
+            &mut *ptr
+        }
+    }
+
+    /// Copies the slice into the arena, returning a reference to the copy.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_copy<T: Copy>(&self, values: &[T]) -> &mut [T] {
+        let layout = Layout::array::<T>(values.len()).expect("Arena allocation is too large.");
+        let ptr = self.alloc_layout(layout) as *mut T;
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), ptr, values.len());
+            slice::from_raw_parts_mut(ptr, values.len())
+        }
+    }
+
+    /// Allocates a slice of the given length in the arena with every element set to the value.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_fill<T: Copy>(&self, len: usize, value: T) -> &mut [T] {
+        let layout = Layout::array::<T>(len).expect("Arena allocation is too large.");
+        let ptr = self.alloc_layout(layout) as *mut T;
+        unsafe {
+            for index in 0..len {
+                ptr::write(ptr.add(index), value);
+            }
+            slice::from_raw_parts_mut(ptr, len)
+        }
+    }
+
+    /// Makes all of the arena's memory available for reuse. Every reference previously handed out
+    /// by the arena is invalidated. The memory itself is kept for future allocations.
+    pub fn reset(&mut self) {
+        self.current.set(0);
+        self.offset.set(0);
+    }
+
+    /// The total number of bytes of memory owned by the arena.
+    pub fn capacity(&self) -> usize {
+        let chunks = unsafe { &*self.chunks.get() };
+        chunks.iter().map(|chunk| chunk.layout.size()).sum()
+    }
+
+    fn alloc_layout(&self, layout: Layout) -> *mut u8 {
+        if layout.size() == 0 {
+            return layout.align() as *mut u8;
+        }
+        // The chunk list is only touched here and in drop, and references into chunks point to
+        // separately allocated memory, so adding chunks doesn't invalidate them.
+        let chunks = unsafe { &mut *self.chunks.get() };
+        loop {
+            let current = self.current.get();
+            if let Some(chunk) = chunks.get(current) {
+                let base = chunk.ptr as usize;
+                let start = align_up(base + self.offset.get(), layout.align()) - base;
+                if start + layout.size() <= chunk.layout.size() {
+                    self.offset.set(start + layout.size());
+                    return unsafe { chunk.ptr.add(start) };
+                }
+                if current + 1 < chunks.len() {
+                    self.current.set(current + 1);
+                    self.offset.set(0);
+                    continue;
+                }
+            }
+
+            let size = self.chunk_size.max(layout.size());
+            let align = layout.align().max(mem::align_of::<usize>());
+            let chunk_layout = Layout::from_size_align(size, align).expect("Arena allocation is too large.");
+            let ptr = unsafe { alloc(chunk_layout) };
+            if ptr.is_null() {
+                handle_alloc_error(chunk_layout);
+            }
+            chunks.push(Chunk {
+                ptr,
+                layout: chunk_layout,
+            });
+            self.current.set(chunks.len() - 1);
+            self.offset.set(0);
+        }
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Arena {
+        Arena::new()
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        for chunk in self.chunks.get_mut().drain(..) {
+            unsafe { dealloc(chunk.ptr, chunk.layout) };
+        }
+    }
+}
+
+#[inline(always)]
+fn align_up(value: usize, align: usize) -> usize {
+    (value + align - 1) & !(align - 1)
+}
+
+// ////////////////////////////////////////////////////////////////////////////
+// Tests
+// ////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alloc() {
+        let arena = Arena::with_chunk_size(64);
+        let a = arena.alloc(1u8);
+        let b = arena.alloc(2u64);
+        let c = arena.alloc_slice_copy(&[3u32, 4, 5]);
+        let d = arena.alloc_slice_fill(100, 6u16);
+        assert_eq!(*a, 1);
+        assert_eq!(*b, 2);
+        assert_eq!(c, &[3, 4, 5]);
+        assert!(d.iter().all(|&value| value == 6));
+        assert_eq!(b as *const u64 as usize % mem::align_of::<u64>(), 0);
+        assert_eq!(arena.alloc(()), &());
+    }
+
+    #[test]
+    fn reset() {
+        let mut arena = Arena::with_chunk_size(64);
+        for value in 0..32u64 {
+            arena.alloc(value);
+        }
+        let capacity = arena.capacity();
+        arena.reset();
+        for value in 0..32u64 {
+            assert_eq!(*arena.alloc(value), value);
+        }
+        assert_eq!(arena.capacity(), capacity);
+    }
+}
@@ -1 +1,4 @@
-pub mod bad;
+mod arena;
+pub(crate) mod bad;
+
+pub use self::arena::*;